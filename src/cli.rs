@@ -1,5 +1,8 @@
 use crate::config::Config;
+use crate::helper::args::export::SpecFormat;
+use crate::helper::docs::serve::DEFAULT_SERVE_ADDRESS;
 use clap::{Parser, Subcommand};
+use clap_complete::Shell;
 use std::path::PathBuf;
 
 /// Command-line arguments.
@@ -36,6 +39,18 @@ pub struct CliArgs {
     /// Enables verbose logging.
     #[arg(short, long)]
     pub verbose: bool,
+    /// Searches the options of the command for the given pattern.
+    #[arg(long, value_name = "PATTERN")]
+    pub grep: Option<String>,
+    /// Uses fuzzy matching instead of regex for searching.
+    #[arg(long, requires = "grep")]
+    pub fuzzy: bool,
+    /// Searches the options of the subcommands as well.
+    #[arg(short, long, requires = "grep")]
+    pub recursive: bool,
+    /// Appends the personal note of the command to the output.
+    #[arg(short, long)]
+    pub note: bool,
     /// Subcommands.
     #[command(subcommand)]
     pub subcommand: Option<CliCommands>,
@@ -54,18 +69,180 @@ pub enum CliCommands {
         /// Use a custom URL for cheat.sh.
         #[arg(long, env = "CHEAT_SH_URL", value_name = "URL")]
         cheat_sh_url: Option<String>,
+        /// Sets the options of the cheat.sh pages (e.g. "Q" to hide the comments).
+        #[arg(long, env = "CHEAT_SH_OPTIONS", value_name = "OPTIONS")]
+        cheat_sh_options: Option<String>,
+        /// Searches the cheat.sh pages of the command for the given keyword.
+        #[arg(short, long, value_name = "KEYWORD", conflicts_with_all = ["examples", "all", "first", "list"])]
+        search: Option<String>,
+        /// Lists the cheat.sh topics in the namespace of the command (e.g. "rust").
+        #[arg(long, conflicts_with_all = ["examples", "all", "first"])]
+        list: bool,
         /// Use a custom provider URL for `eg` pages.
         #[arg(long, env = "EG_PAGES_URL", value_name = "URL")]
         eg_url: Option<String>,
         /// Use a custom URL for cheat sheets.
         #[arg(long, env = "CHEATSHEETS_URL", value_name = "URL")]
         cheat_url: Option<String>,
+        /// Use a custom URL for tldr pages.
+        #[arg(long, env = "TLDR_PAGES_URL", value_name = "URL")]
+        tldr_url: Option<String>,
         /// Sets the pager to use.
         #[arg(short, long)]
         pager: Option<String>,
         /// Disables the pager.
         #[arg(long)]
         no_pager: bool,
+        /// Shows only the examples from the man page, eg and cheat.sh.
+        #[arg(short, long)]
+        examples: bool,
+        /// Includes the SYNOPSIS section in the examples of the man page.
+        #[arg(long)]
+        synopsis: bool,
+        /// Shows the man page synopsis and the examples from all providers as a single page.
+        #[arg(short, long, conflicts_with = "examples")]
+        all: bool,
+        /// Shows the page of the first provider that has one (see the `fallback` option).
+        #[arg(short, long, conflicts_with_all = ["examples", "all"])]
+        first: bool,
+        /// Prints the pages of the given providers without prompting (e.g. "cheat.sh,eg").
+        ///
+        /// With `--first`, the providers are tried in the given order.
+        #[arg(long, value_delimiter = ',', value_name = "PROVIDERS")]
+        provider: Vec<String>,
+    },
+    /// Explain a command line flag by flag.
+    Explain {
+        /// Command line to explain (e.g. "tar -xzvf a.tgz -C out").
+        #[arg(
+            required = true,
+            value_name = "COMMAND_LINE",
+            trailing_var_arg = true,
+            allow_hyphen_values = true
+        )]
+        cmdline: Vec<String>,
+    },
+    /// Build or search the local index of options.
+    Index {
+        /// Sets the index file.
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+        /// Index subcommands.
+        #[command(subcommand)]
+        command: IndexCommand,
+    },
+    /// Generate shell completions for a command from its help.
+    Completions {
+        /// Command or binary name.
+        cmd: String,
+        /// Sets the shell to generate the completions for.
+        #[arg(short, long, value_enum)]
+        shell: Shell,
+        /// Probes the help of the subcommands as well.
+        #[arg(short, long)]
+        recursive: bool,
+    },
+    /// Generate a man page for a command from its help.
+    Mangen {
+        /// Command or binary name.
+        cmd: String,
+        /// Sets the section of the man page.
+        #[arg(short, long, default_value = "1")]
+        section: String,
+        /// Writes the man page to the given file.
+        #[arg(short, long, value_name = "PATH", conflicts_with = "install")]
+        output: Option<PathBuf>,
+        /// Installs the man page into the user manpath.
+        #[arg(short, long)]
+        install: bool,
+        /// Probes the help of the subcommands as well.
+        #[arg(short, long)]
+        recursive: bool,
+    },
+    /// Export the parsed help of a command as a CLI specification.
+    Export {
+        /// Command or binary name.
+        cmd: String,
+        /// Sets the format of the specification.
+        #[arg(short, long, value_enum, default_value_t = SpecFormat::Json)]
+        format: SpecFormat,
+        /// Writes the specification to the given file.
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Probes the help of the subcommands as well.
+        #[arg(short, long)]
+        recursive: bool,
+    },
+    /// Download the provider pages into the local mirror for offline use.
+    Sync {
+        /// Providers to sync (defaults to cheatsheets, eg, tldr and cheat.sh).
+        providers: Vec<String>,
+    },
+    /// Show and navigate the GNU info page of a command.
+    Info {
+        /// Command or node (e.g. `ls` or `(coreutils)Top`).
+        query: String,
+    },
+    /// Search the NAME sections of the manual pages.
+    Apropos {
+        /// Keyword to search for (a case-insensitive regular expression).
+        keyword: String,
+    },
+    /// List the local cheat sheets.
+    Sheets {
+        /// Lists only the sheets with the given tag.
+        #[arg(short, long)]
+        tag: Option<String>,
+    },
+    /// Serve the local mirror over HTTP.
+    Serve {
+        /// Directory to serve (defaults to the local mirror).
+        #[arg(short, long, value_name = "DIR")]
+        mirror: Option<PathBuf>,
+        /// Sets the address to listen on.
+        #[arg(short, long, default_value = DEFAULT_SERVE_ADDRESS)]
+        address: String,
+    },
+    /// Edit or export the personal notes.
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Note {
+        /// Command or binary name.
+        #[arg(required = true)]
+        cmd: Option<String>,
+        /// Note subcommands.
+        #[command(subcommand)]
+        command: Option<NoteCommand>,
+    },
+    /// List or search the provider plugins.
+    Plugins {
+        /// Plugin subcommands.
+        #[command(subcommand)]
+        command: Option<PluginCommand>,
+    },
+}
+
+/// Subcommands of the index.
+#[derive(Debug, Subcommand)]
+pub enum IndexCommand {
+    /// Probe the help of the commands and build the index.
+    Build {
+        /// Commands to index.
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        cmds: Vec<String>,
+        /// Indexes all executables in `PATH`.
+        #[arg(short, long)]
+        all: bool,
+        /// Indexes the subcommands as well.
+        #[arg(short, long)]
+        recursive: bool,
+    },
+    /// Search the options in the index.
+    Search {
+        /// Search query (e.g. "follow symlinks").
+        query: String,
+        /// Sets the maximum number of results.
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
     },
 }
 
@@ -88,21 +265,33 @@ impl CliArgs {
         if let Some(CliCommands::Plz {
             ref man_cmd,
             ref cheat_sh_url,
+            ref cheat_sh_options,
             ref eg_url,
+            ref tldr_url,
             no_pager,
             ref pager,
+            synopsis,
             ..
         }) = self.subcommand
         {
+            if synopsis {
+                config.man_synopsis = Some(true);
+            }
             if let Some(man_cmd) = man_cmd {
                 config.man_command.clone_from(man_cmd);
             }
             if let Some(cheat_sh_url) = cheat_sh_url {
                 config.cheat_sh_url = Some(cheat_sh_url.clone());
             }
+            if let Some(cheat_sh_options) = cheat_sh_options {
+                config.cheat_sh_options = Some(cheat_sh_options.clone());
+            }
             if let Some(eg_url) = eg_url {
                 config.eg_url = Some(eg_url.to_owned());
             }
+            if let Some(tldr_url) = tldr_url {
+                config.tldr_url = Some(tldr_url.to_owned());
+            }
             if no_pager {
                 config.pager_command = None;
             } else if let Some(pager) = pager {
@@ -112,6 +301,29 @@ impl CliArgs {
     }
 }
 
+/// Subcommands of the notes.
+#[derive(Debug, Subcommand)]
+pub enum NoteCommand {
+    /// Export the notes in the cheatsheets format.
+    Export {
+        /// Directory to export the notes into.
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+    },
+}
+
+/// Subcommands of the plugins.
+#[derive(Debug, Subcommand)]
+pub enum PluginCommand {
+    /// List the available plugins (default).
+    List,
+    /// Search the pages of the plugins.
+    Search {
+        /// Search query.
+        query: String,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,15 +344,26 @@ mod tests {
                 cmd: "ps".to_string(),
                 pager: Some("bat".to_string()),
                 cheat_sh_url: None,
+                cheat_sh_options: Some("Q".to_string()),
+                search: None,
+                list: false,
                 cheat_url: None,
                 eg_url: None,
+                tldr_url: None,
                 man_cmd: None,
                 no_pager: false,
+                examples: true,
+                synopsis: true,
+                all: false,
+                first: false,
+                provider: Vec::new(),
             }),
             ..Default::default()
         };
         args.update_config(&mut config);
         assert!(config.check_help);
         assert_eq!(Some(String::from("bat")), config.pager_command);
+        assert_eq!(Some(true), config.man_synopsis);
+        assert_eq!(Some("Q"), config.cheat_sh_options.as_deref());
     }
 }
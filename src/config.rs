@@ -1,11 +1,15 @@
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::helper::args::common::{HelpArg, VersionArg};
 use crate::helper::args::FOUND_EMOTICON;
+use crate::helper::docs::cache::DEFAULT_CACHE_TTL;
 use crate::helper::docs::cheat_sh::DEFAULT_CHEAT_SHEET_PROVIDER;
 use crate::helper::docs::cheatsheets::DEFAULT_CHEATSHEETS_PROVIDER;
 use crate::helper::docs::eg::DEFAULT_EG_PAGES_PROVIDER;
+use crate::helper::docs::tldr::DEFAULT_TLDR_PROVIDER;
+use crate::helper::docs::ContentFormat;
 use colored::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::io::Write;
@@ -23,16 +27,91 @@ pub struct Config {
     pub check_args: Option<Vec<Vec<String>>>,
     /// Command to run for manual pages.
     pub man_command: String,
+    /// Include the SYNOPSIS section in the examples of the manual pages.
+    pub man_synopsis: Option<bool>,
     /// Pager to use for command outputs, None to disable.
     pub pager_command: Option<String>,
     /// Use a custom URL for cheat.sh.
     pub cheat_sh_url: Option<String>,
+    /// Options of the cheat.sh pages (e.g. `Q` to hide the comments).
+    pub cheat_sh_options: Option<String>,
     /// Use a custom URL for `eg` pages provider.
     pub eg_url: Option<String>,
     /// Use a custom URL for cheatsheets provider.
     pub cheatsheets_url: Option<String>,
-    /// Timeout for running the commands.
+    /// Use a custom URL for tldr pages provider.
+    pub tldr_url: Option<String>,
+    /// Platform of the tldr pages (e.g. `linux`, `osx`), defaults to the current platform.
+    pub tldr_platform: Option<String>,
+    /// Language of the tldr pages (e.g. `de`, `pt_BR`).
+    pub tldr_language: Option<String>,
+    /// Path of the local tldr pages archive (zip file or directory) for offline use.
+    pub tldr_archive: Option<PathBuf>,
+    /// Timeout for running the commands and the provider requests (in seconds).
     pub timeout: Option<u64>,
+    /// Time-to-live of the cached provider pages in seconds.
+    pub cache_ttl: Option<u64>,
+    /// Directory of the local provider mirror (see `halp sync`).
+    pub mirror_dir: Option<PathBuf>,
+    /// Directory of the personal notes (see `halp note`).
+    pub notes_dir: Option<PathBuf>,
+    /// cheat.sh topics to download into the local mirror.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cheat_sh_topics: Vec<String>,
+    /// Providers to try in order with `halp plz --first`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback: Vec<String>,
+    /// Directories of the local cheat sheets in the order of precedence.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cheatpaths: Vec<CheatPath>,
+    /// User-defined help providers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<ProviderConfig>,
+    /// Provider plugin executables (in addition to the `halp-provider-*` executables in `PATH`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PathBuf>,
+}
+
+/// Configuration of a user-defined help provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    /// Name of the provider.
+    pub name: String,
+    /// Source of the pages.
+    #[serde(flatten)]
+    pub kind: ProviderKind,
+    /// Additional HTTP headers to send (only for `url` providers).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Regex that matches the pages which mean that the page is not found.
+    pub not_found: Option<String>,
+    /// Format of the pages.
+    #[serde(default)]
+    pub format: ContentFormat,
+}
+
+/// Directory of `cheat`-compatible cheat sheets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheatPath {
+    /// Name of the cheat path (e.g. `personal`).
+    pub name: String,
+    /// Directory of the sheets.
+    pub path: PathBuf,
+    /// Tags that are applied to all sheets in the directory.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Source of the pages for a user-defined help provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    /// URL template with a `{cmd}` placeholder.
+    Url(String),
+    /// Command template with a `{cmd}` placeholder.
+    Command(String),
+    /// Directory that contains the pages.
+    Directory(PathBuf),
 }
 
 impl Default for Config {
@@ -51,11 +130,25 @@ impl Default for Config {
                     .collect(),
             ]),
             man_command: "man".to_string(),
+            man_synopsis: None,
             pager_command: Some("less -R".to_string()),
             cheat_sh_url: Some(DEFAULT_CHEAT_SHEET_PROVIDER.to_string()),
+            cheat_sh_options: None,
             eg_url: Some(DEFAULT_EG_PAGES_PROVIDER.to_string()),
             cheatsheets_url: Some(DEFAULT_CHEATSHEETS_PROVIDER.to_string()),
+            tldr_url: Some(DEFAULT_TLDR_PROVIDER.to_string()),
+            tldr_platform: None,
+            tldr_language: None,
+            tldr_archive: None,
             timeout: Some(5),
+            cache_ttl: Some(DEFAULT_CACHE_TTL),
+            mirror_dir: None,
+            notes_dir: None,
+            cheat_sh_topics: Vec::new(),
+            fallback: Vec::new(),
+            cheatpaths: Vec::new(),
+            providers: Vec::new(),
+            plugins: Vec::new(),
         }
     }
 }
@@ -84,14 +177,21 @@ impl Config {
         if let Some(config_dir) = dirs::config_dir() {
             let file_name = concat!(env!("CARGO_PKG_NAME"), ".toml");
             return Some(vec![
-                config_dir.join(&file_name),
-                config_dir.join(env!("CARGO_PKG_NAME")).join(&file_name), // XDG style
+                config_dir.join(file_name),
+                config_dir.join(env!("CARGO_PKG_NAME")).join(file_name), // XDG style
                 config_dir.join(env!("CARGO_PKG_NAME")).join("config"),
             ]);
         }
         None
     }
 
+    /// Returns the data directory (e.g. `<data_dir>/halp`).
+    pub fn get_data_dir() -> Result<PathBuf> {
+        dirs::data_dir()
+            .map(|data_dir| data_dir.join(env!("CARGO_PKG_NAME")))
+            .ok_or(Error::DataDirError)
+    }
+
     /// Parses the configuration file.
     pub fn parse(file: &Path) -> Result<Config> {
         let contents = fs::read_to_string(file)?;
@@ -167,4 +267,50 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn test_parse_providers() -> Result<()> {
+        let config: Config = toml::from_str(
+            r#"
+            check_version = true
+            check_help = true
+            man_command = "man"
+
+            [[providers]]
+            name = "wiki"
+            url = "https://wiki.example.com/cli/{cmd}"
+            headers = { Authorization = "Bearer token" }
+            not_found = "^Page not found"
+            format = "html"
+
+            [[providers]]
+            name = "team"
+            directory = "/opt/team/cheats"
+            "#,
+        )?;
+        assert_eq!(
+            vec![
+                ProviderConfig {
+                    name: String::from("wiki"),
+                    kind: ProviderKind::Url(String::from("https://wiki.example.com/cli/{cmd}")),
+                    headers: BTreeMap::from([(
+                        String::from("Authorization"),
+                        String::from("Bearer token")
+                    )]),
+                    not_found: Some(String::from("^Page not found")),
+                    format: ContentFormat::Html,
+                },
+                ProviderConfig {
+                    name: String::from("team"),
+                    kind: ProviderKind::Directory(PathBuf::from("/opt/team/cheats")),
+                    headers: BTreeMap::new(),
+                    not_found: None,
+                    format: ContentFormat::Plain,
+                },
+            ],
+            config.providers
+        );
+        assert_eq!(config, toml::from_str(&toml::to_string(&config)?)?);
+        Ok(())
+    }
 }
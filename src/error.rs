@@ -1,3 +1,5 @@
+use std::fmt;
+use std::io::ErrorKind as IoErrorKind;
 use thiserror::Error as ThisError;
 
 /// Custom error type.
@@ -27,6 +29,96 @@ pub enum Error {
     /// Error that might occur when the command times out.
     #[error("Command timed out after {0} seconds x_x")]
     TimeoutError(u64),
+    /// Error that might occur while serializing/deserializing JSON.
+    #[error("JSON error: `{0}`")]
+    JsonError(#[from] serde_json::Error),
+    /// Error that might occur when the data directory cannot be determined.
+    #[error("Cannot determine the data directory.")]
+    DataDirError,
+    /// Error that might occur when the home directory cannot be determined.
+    #[error("Cannot determine the home directory.")]
+    HomeDirError,
+    /// Error that might occur when the man page section is invalid.
+    #[error("Invalid man page section: `{0}` (expected e.g. `1` or `3p`)")]
+    ManSectionError(String),
+    /// Error that might occur when the help output of a command cannot be found.
+    #[error("No options or subcommands found in the help output of `{0}`")]
+    HelpNotFoundError(String),
+    /// Error that might occur while parsing regular expressions.
+    #[error("Regex error: `{0}`")]
+    RegexError(#[from] regex::Error),
+    /// Error that might occur when a worker thread panics.
+    #[error("The {0} thread panicked")]
+    PanicError(String),
+    /// Error that might occur when the provider responds with a server error.
+    #[error("Provider server error (HTTP {0})")]
+    ServerError(u16),
+    /// Error that might occur when the provider has no page for the query.
+    #[error("{0}")]
+    NotFoundError(String),
+}
+
+/// Kind of the error that occurred while fetching a page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The provider has no page.
+    NotFound,
+    /// The provider cannot be reached.
+    Offline,
+    /// The provider did not respond in time.
+    Timeout,
+    /// The provider failed to serve the page.
+    Server,
+    /// Any other error.
+    Other,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::NotFound => "not found",
+            Self::Offline => "offline",
+            Self::Timeout => "timed out",
+            Self::Server => "server error",
+            Self::Other => "failed",
+        })
+    }
+}
+
+impl Error {
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NotFoundError(_) => ErrorKind::NotFound,
+            Self::TimeoutError(_) => ErrorKind::Timeout,
+            Self::ServerError(_) => ErrorKind::Server,
+            Self::IoError(e) => io_error_kind(e),
+            Self::RequestError(e) => match e.as_ref() {
+                ureq::Error::StatusCode(404 | 410) => ErrorKind::NotFound,
+                ureq::Error::StatusCode(code) if *code == 429 || *code >= 500 => ErrorKind::Server,
+                ureq::Error::Timeout(_) => ErrorKind::Timeout,
+                ureq::Error::HostNotFound | ureq::Error::ConnectionFailed => ErrorKind::Offline,
+                ureq::Error::Io(e) if e.kind() == IoErrorKind::TimedOut => ErrorKind::Timeout,
+                // Network I/O errors (e.g. failed DNS lookups) mean that the provider is unreachable.
+                ureq::Error::Io(_) => ErrorKind::Offline,
+                _ => ErrorKind::Other,
+            },
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Returns the kind of the I/O error.
+fn io_error_kind(error: &std::io::Error) -> ErrorKind {
+    match error.kind() {
+        IoErrorKind::TimedOut => ErrorKind::Timeout,
+        IoErrorKind::ConnectionRefused
+        | IoErrorKind::ConnectionReset
+        | IoErrorKind::ConnectionAborted
+        | IoErrorKind::NotConnected
+        | IoErrorKind::AddrNotAvailable => ErrorKind::Offline,
+        _ => ErrorKind::Other,
+    }
 }
 
 /// Type alias for the standard [`Result`] type.
@@ -36,16 +128,44 @@ pub type Result<T> = std::result::Result<T, Error>;
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
-    use std::io::{Error as IoError, ErrorKind};
+    use std::io::Error as IoError;
 
     #[test]
     fn test_error() {
         let message = "your computer is on fire!";
-        let error = Error::from(IoError::new(ErrorKind::Other, message));
+        let error = Error::from(IoError::other(message));
         assert_eq!(format!("IO error: `{message}`"), error.to_string());
         assert_eq!(
             format!("\"IO error: `{message}`\""),
             format!("{:?}", error.to_string())
         );
+        assert_eq!(ErrorKind::Other, error.kind());
+        assert_eq!(
+            ErrorKind::NotFound,
+            Error::NotFoundError(String::from("Unknown topic.")).kind()
+        );
+        assert_eq!(
+            ErrorKind::Other,
+            Error::ProviderError(String::from("Invalid archive")).kind()
+        );
+        assert_eq!(ErrorKind::Timeout, Error::TimeoutError(5).kind());
+        assert_eq!(ErrorKind::Server, Error::ServerError(502).kind());
+        assert_eq!(
+            ErrorKind::Offline,
+            Error::from(Box::new(ureq::Error::HostNotFound)).kind()
+        );
+        assert_eq!(
+            ErrorKind::NotFound,
+            Error::from(Box::new(ureq::Error::StatusCode(404))).kind()
+        );
+        assert_eq!(
+            ErrorKind::Server,
+            Error::from(Box::new(ureq::Error::StatusCode(429))).kind()
+        );
+        assert_eq!(
+            ErrorKind::Other,
+            Error::from(Box::new(ureq::Error::StatusCode(403))).kind()
+        );
+        assert_eq!("timed out", ErrorKind::Timeout.to_string());
     }
 }
@@ -0,0 +1,220 @@
+use crate::helper::args::parser::{HelpOption, ParsedHelp};
+use clap::{Arg, ArgAction, Command, ValueHint};
+use std::collections::HashSet;
+
+/// Flags that are already used in a command.
+#[derive(Default)]
+struct UsedFlags {
+    /// Argument IDs.
+    ids: HashSet<String>,
+    /// Long flags.
+    longs: HashSet<String>,
+    /// Short flags.
+    shorts: HashSet<char>,
+}
+
+/// Builds a [`Command`] from the parsed help of a command.
+///
+/// This makes it possible to use the `clap` ecosystem (e.g. completions and man pages) for tools
+/// that are not built with `clap`. Flags that cannot be represented (e.g. `-name` of `find`) are
+/// skipped.
+pub fn build_command(name: &str, about: Option<&str>, help: &ParsedHelp) -> Command {
+    let mut command = Command::new(name.to_string())
+        .disable_help_flag(true)
+        .disable_version_flag(true)
+        .disable_help_subcommand(true);
+    if let Some(about) = about.filter(|about| !about.is_empty()) {
+        command = command.about(about.to_string());
+    }
+    if let Some(ref usage) = help.usage {
+        command = command.override_usage(usage.to_string());
+    }
+    let mut used = UsedFlags::default();
+    for option in &help.options {
+        if let Some(arg) = build_arg(option, &mut used) {
+            command = command.arg(arg);
+        }
+    }
+    let mut names = HashSet::new();
+    for subcommand in &help.subcommands {
+        if !names.insert(subcommand.name.as_str()) {
+            continue;
+        }
+        let subcommand_help = subcommand.help.clone().unwrap_or_default();
+        command = command.subcommand(build_command(
+            &subcommand.name,
+            Some(&subcommand.description),
+            &subcommand_help,
+        ));
+    }
+    command
+}
+
+/// Builds an [`Arg`] from the given option.
+fn build_arg(option: &HelpOption, used: &mut UsedFlags) -> Option<Arg> {
+    let longs = option
+        .flags
+        .iter()
+        .filter_map(|flag| flag.strip_prefix("--"))
+        .filter(|long| !used.longs.contains(*long))
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let shorts = option
+        .flags
+        .iter()
+        .filter_map(|flag| {
+            let mut chars = flag.strip_prefix('-')?.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '-' => Some(c),
+                _ => None,
+            }
+        })
+        .filter(|short| !used.shorts.contains(short))
+        .collect::<Vec<_>>();
+    let id = longs
+        .first()
+        .cloned()
+        .or_else(|| shorts.first().map(|c| c.to_string()))?;
+    if !used.ids.insert(id.to_string()) {
+        return None;
+    }
+    let mut arg = Arg::new(id).help(option.description.to_string());
+    if let Some((long, aliases)) = longs.split_first() {
+        arg = arg
+            .long(long.to_string())
+            .visible_aliases(aliases.iter().map(String::from));
+    }
+    if let Some((short, aliases)) = shorts.split_first() {
+        arg = arg
+            .short(*short)
+            .visible_short_aliases(aliases.iter().copied());
+    }
+    used.longs.extend(longs);
+    used.shorts.extend(shorts);
+    arg = match option.value {
+        Some(ref value) => {
+            let value_name = value
+                .trim_matches(|c| "[]<>=".contains(c))
+                .split([' ', '|'])
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let arg = arg
+                .action(ArgAction::Set)
+                .value_hint(get_value_hint(&value_name));
+            let arg = if value_name.is_empty() {
+                arg
+            } else {
+                arg.value_name(value_name)
+            };
+            if option.takes_value() {
+                arg
+            } else {
+                arg.num_args(0..=1).require_equals(true)
+            }
+        }
+        None => arg.action(ArgAction::SetTrue),
+    };
+    Some(arg)
+}
+
+/// Guesses the value hint from the value name (e.g. `FILE`).
+fn get_value_hint(value_name: &str) -> ValueHint {
+    let value_name = value_name.to_lowercase();
+    if value_name.contains("dir") {
+        ValueHint::DirPath
+    } else if ["file", "path", "archive"]
+        .iter()
+        .any(|name| value_name.contains(name))
+    {
+        ValueHint::FilePath
+    } else if value_name.contains("url") {
+        ValueHint::Url
+    } else if ["user", "owner"]
+        .iter()
+        .any(|name| value_name.contains(name))
+    {
+        ValueHint::Username
+    } else if value_name.contains("host") {
+        ValueHint::Hostname
+    } else if ["cmd", "command", "prog"]
+        .iter()
+        .any(|name| value_name.contains(name))
+    {
+        ValueHint::CommandName
+    } else {
+        ValueHint::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_build_command() {
+        let mut help = ParsedHelp::parse(
+            r"Usage: tar [OPTION...] [FILE]...
+  -C, --directory=DIR        change to directory DIR
+      --color[=WHEN]         colorize the output
+  -x, --extract, --get       extract files from an archive
+  -X                         duplicated short flag
+  -x                         conflicting flag
+  -name pattern              single-dash long flag
+
+Commands:
+  list  List the archive
+  list  Duplicated",
+        );
+        help.subcommands[0].help = Some(ParsedHelp::parse("  -v  verbose"));
+        let command = build_command("tar", Some("tape archiver"), &help);
+        command.clone().debug_assert();
+        let args = command
+            .get_arguments()
+            .map(|arg| {
+                (
+                    arg.get_id().to_string(),
+                    arg.get_short(),
+                    arg.get_long().map(String::from),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (
+                    String::from("directory"),
+                    Some('C'),
+                    Some(String::from("directory"))
+                ),
+                (String::from("color"), None, Some(String::from("color"))),
+                (
+                    String::from("extract"),
+                    Some('x'),
+                    Some(String::from("extract"))
+                ),
+                (String::from("X"), Some('X'), None),
+            ],
+            args
+        );
+        let directory = command
+            .get_arguments()
+            .find(|arg| arg.get_id() == "directory")
+            .expect("argument not found");
+        assert_eq!(ValueHint::DirPath, directory.get_value_hint());
+        assert_eq!(
+            vec!["list"],
+            command
+                .get_subcommands()
+                .map(|s| s.get_name())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            1,
+            command
+                .find_subcommand("list")
+                .map(|s| s.get_arguments().count())
+                .unwrap_or_default()
+        );
+    }
+}
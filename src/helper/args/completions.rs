@@ -0,0 +1,54 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::helper::args::command::build_command;
+use crate::helper::args::get_required_help;
+use clap_complete::Shell;
+use std::io::Write;
+use std::path::Path;
+
+/// Returns the binary name of the given command (e.g. `ls` for `/usr/bin/ls`).
+pub fn get_bin_name(cmd: &str) -> String {
+    Path::new(cmd)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| cmd.to_string())
+}
+
+/// Generates a shell completion script for the given command from its help output.
+pub fn generate_completions<Output: Write>(
+    cmd: &str,
+    shell: Shell,
+    recursive: bool,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let help = get_required_help(cmd, recursive, config)?;
+    let bin_name = get_bin_name(cmd);
+    let mut command = build_command(&bin_name, None, &help);
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut command, bin_name, &mut script);
+    output.write_all(&script)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_generate_completions() -> Result<()> {
+        let bin = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join("debug")
+            .join(concat!(env!("CARGO_PKG_NAME"), "-test"))
+            .to_string_lossy()
+            .to_string();
+        let mut output = Vec::new();
+        generate_completions(&bin, Shell::Fish, false, &Config::default(), &mut output)?;
+        let script = String::from_utf8_lossy(&output);
+        assert!(script.contains("complete -c halp-test -s h -l help -d 'Print help'"));
+        assert!(script.contains("complete -c halp-test -s V -l version -d 'Print version'"));
+        Ok(())
+    }
+}
@@ -0,0 +1,360 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::helper::args::parser::ParsedHelp;
+use crate::helper::args::{get_parsed_help, CHECK_EMOTICON, FAIL_EMOTICON, OUTPUT_SEPARATOR};
+use crate::helper::docs::man::{extract_section, get_man_page};
+use colored::*;
+use std::io::Write;
+
+/// A single explained part of a command line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Explained {
+    /// Program to run.
+    Program(String),
+    /// Subcommand of the program.
+    Subcommand {
+        /// Name of the subcommand.
+        name: String,
+        /// Description of the subcommand.
+        description: String,
+    },
+    /// Option (flag) with an optional value.
+    Option {
+        /// Flag.
+        flag: String,
+        /// Value of the flag.
+        value: Option<String>,
+        /// Description of the flag, `None` if it is unknown.
+        description: Option<String>,
+    },
+    /// Positional argument.
+    Argument(String),
+    /// End of the options (`--`).
+    EndOfOptions,
+}
+
+impl Explained {
+    /// Returns the part of the command line that is explained.
+    fn token(&self) -> String {
+        match self {
+            Self::Program(name) | Self::Argument(name) => name.to_string(),
+            Self::Subcommand { name, .. } => name.to_string(),
+            Self::Option { flag, value, .. } => match value {
+                Some(value) => format!("{flag} {value}"),
+                None => flag.to_string(),
+            },
+            Self::EndOfOptions => String::from("--"),
+        }
+    }
+
+    /// Returns the explanation.
+    fn explanation(&self) -> Option<String> {
+        match self {
+            Self::Program(_) => Some(String::from("program")),
+            Self::Subcommand { description, .. } => Some(if description.is_empty() {
+                String::from("subcommand")
+            } else {
+                description.to_string()
+            }),
+            Self::Option { description, .. } => description.clone(),
+            Self::Argument(_) => Some(String::from("argument")),
+            Self::EndOfOptions => Some(String::from("end of options")),
+        }
+    }
+}
+
+/// Splits the given command line into tokens like a POSIX shell.
+///
+/// Single quotes, double quotes and backslash escapes are supported.
+pub fn split_command_line(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token: Option<String> = None;
+    let mut chars = line.chars();
+    let mut quote: Option<char> = None;
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('"'), '\\') => match chars.next() {
+                Some(next @ ('"' | '\\' | '$' | '`')) => {
+                    token.get_or_insert_with(String::new).push(next)
+                }
+                Some(next) => {
+                    let token = token.get_or_insert_with(String::new);
+                    token.push('\\');
+                    token.push(next);
+                }
+                None => token.get_or_insert_with(String::new).push('\\'),
+            },
+            (Some(_), c) => token.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                token.get_or_insert_with(String::new);
+            }
+            (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    token.get_or_insert_with(String::new).push(next);
+                }
+            }
+            (None, c) if c.is_whitespace() => tokens.extend(token.take()),
+            (None, c) => token.get_or_insert_with(String::new).push(c),
+        }
+    }
+    tokens.extend(token);
+    tokens
+}
+
+/// Explains the given tokens of a command line.
+///
+/// `load_help` is used for loading the help of the program (and its subcommands) while
+/// `load_fallback` is used for loading additional options when a flag is not found in the help.
+pub fn explain_tokens<HelpLoader, FallbackLoader>(
+    tokens: &[String],
+    mut load_help: HelpLoader,
+    mut load_fallback: FallbackLoader,
+) -> Result<Vec<Explained>>
+where
+    HelpLoader: FnMut(&str) -> Result<ParsedHelp>,
+    FallbackLoader: FnMut(&str) -> Result<ParsedHelp>,
+{
+    let Some((program, args)) = tokens.split_first() else {
+        return Ok(Vec::new());
+    };
+    let mut cmd = program.to_string();
+    let mut help = load_help(&cmd)?;
+    let mut fallback_loaded = false;
+    let mut explained = vec![Explained::Program(program.to_string())];
+    let mut options_ended = false;
+    let mut positional_found = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if options_ended || !arg.starts_with('-') || arg == "-" {
+            if !options_ended && !positional_found {
+                if let Some(subcommand) = help.find_subcommand(arg) {
+                    explained.push(Explained::Subcommand {
+                        name: arg.to_string(),
+                        description: subcommand.description.to_string(),
+                    });
+                    cmd = format!("{cmd} {arg}");
+                    help = load_help(&cmd)?;
+                    fallback_loaded = false;
+                    continue;
+                }
+            }
+            positional_found = true;
+            explained.push(Explained::Argument(arg.to_string()));
+            continue;
+        }
+        if arg == "--" {
+            options_ended = true;
+            explained.push(Explained::EndOfOptions);
+            continue;
+        }
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        if help.find_option(flag).is_none() && !fallback_loaded {
+            help.merge(load_fallback(&cmd)?);
+            fallback_loaded = true;
+        }
+        if let Some(option) = help.find_option(flag) {
+            let value = match inline_value {
+                Some(value) => Some(value),
+                None if option.takes_value() => args.next().cloned(),
+                None => None,
+            };
+            explained.push(Explained::Option {
+                flag: flag.to_string(),
+                value,
+                description: Some(option.description.to_string()),
+            });
+        } else if !flag.starts_with("--") && flag.len() > 2 {
+            let bundle = &flag[1..];
+            for (i, c) in bundle.char_indices() {
+                let short = format!("-{c}");
+                let option = help.find_option(&short);
+                if option.is_some_and(|option| option.takes_value()) {
+                    let rest = &bundle[i + c.len_utf8()..];
+                    let value = if rest.is_empty() {
+                        args.next().cloned()
+                    } else {
+                        Some(rest.to_string())
+                    };
+                    explained.push(Explained::Option {
+                        flag: short,
+                        value,
+                        description: option.map(|option| option.description.to_string()),
+                    });
+                    break;
+                }
+                explained.push(Explained::Option {
+                    flag: short,
+                    value: None,
+                    description: option.map(|option| option.description.to_string()),
+                });
+            }
+        } else {
+            explained.push(Explained::Option {
+                flag: flag.to_string(),
+                value: inline_value,
+                description: None,
+            });
+        }
+    }
+    Ok(explained)
+}
+
+/// Explains the given command line flag by flag.
+///
+/// The help output of the program is used for the descriptions and the `OPTIONS` (or
+/// `DESCRIPTION`) section of the man page is used as a fallback.
+pub fn explain_command_line<Output: Write>(
+    cmdline: &[String],
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let tokens = match cmdline {
+        [line] => split_command_line(line),
+        tokens => tokens.to_vec(),
+    };
+    if tokens.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        output,
+        "{}  {} '{}'",
+        CHECK_EMOTICON.magenta(),
+        "explaining".green().bold(),
+        tokens.join(" ").white().italic()
+    )?;
+    let explained = explain_tokens(
+        &tokens,
+        |cmd| get_parsed_help(cmd, config),
+        |cmd| {
+            let page = get_man_page(&config.man_command, &cmd.replace(' ', "-"))
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            Ok(ParsedHelp::parse(
+                &extract_section(&page, "OPTIONS")
+                    .or_else(|| extract_section(&page, "DESCRIPTION"))
+                    .unwrap_or_default(),
+            ))
+        },
+    )?;
+    let width = explained
+        .iter()
+        .map(|e| e.token().chars().count())
+        .max()
+        .unwrap_or_default();
+    writeln!(output, "{}", OUTPUT_SEPARATOR.bright_black())?;
+    for part in explained {
+        let token = format!("{:width$}", part.token());
+        let token = match part {
+            Explained::Program(_) | Explained::Subcommand { .. } => token.magenta().bold(),
+            Explained::Option { .. } | Explained::EndOfOptions => token.cyan().bold(),
+            Explained::Argument(_) => token.white().italic(),
+        };
+        match part.explanation() {
+            Some(explanation) => writeln!(output, "{}  {}", token, explanation)?,
+            None => writeln!(
+                output,
+                "{}  {} {}",
+                token,
+                FAIL_EMOTICON.magenta(),
+                "unknown option".red()
+            )?,
+        }
+    }
+    writeln!(output, "{}", OUTPUT_SEPARATOR.bright_black())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const TAR_HELP: &str = r"Usage: tar [OPTION...] [FILE]...
+  -C, --directory=DIR        change to directory DIR
+  -f, --file=ARCHIVE         use archive file or device ARCHIVE
+  -v, --verbose              verbosely list files processed
+  -x, --extract, --get       extract files from an archive";
+
+    fn option(flag: &str, value: Option<&str>, description: Option<&str>) -> Explained {
+        Explained::Option {
+            flag: flag.to_string(),
+            value: value.map(String::from),
+            description: description.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            vec!["git", "commit", "-m", "it's done", "a b", "c\\d", ""],
+            split_command_line(r#"git commit -m "it's done" a\ b 'c\d' """#)
+        );
+    }
+
+    #[test]
+    fn test_explain_tokens() -> Result<()> {
+        let tokens = split_command_line("tar -xzvf a.tgz --directory=out -- -file");
+        let explained = explain_tokens(
+            &tokens,
+            |_| Ok(ParsedHelp::parse(TAR_HELP)),
+            |_| {
+                Ok(ParsedHelp::parse(
+                    "  -z, --gzip  filter the archive through gzip",
+                ))
+            },
+        )?;
+        assert_eq!(
+            vec![
+                Explained::Program(String::from("tar")),
+                option("-x", None, Some("extract files from an archive")),
+                option("-z", None, Some("filter the archive through gzip")),
+                option("-v", None, Some("verbosely list files processed")),
+                option(
+                    "-f",
+                    Some("a.tgz"),
+                    Some("use archive file or device ARCHIVE")
+                ),
+                option("--directory", Some("out"), Some("change to directory DIR")),
+                Explained::EndOfOptions,
+                Explained::Argument(String::from("-file")),
+            ],
+            explained
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_subcommand() -> Result<()> {
+        let tokens = split_command_line("git commit -q -m msg file");
+        let explained = explain_tokens(
+            &tokens,
+            |cmd| {
+                Ok(ParsedHelp::parse(match cmd {
+                    "git" => "Commands:\n  commit  Record changes to the repository",
+                    _ => "  -m, --message <msg>  commit message",
+                }))
+            },
+            |_| Ok(ParsedHelp::default()),
+        )?;
+        assert_eq!(
+            vec![
+                Explained::Program(String::from("git")),
+                Explained::Subcommand {
+                    name: String::from("commit"),
+                    description: String::from("Record changes to the repository"),
+                },
+                option("-q", None, None),
+                option("-m", Some("msg"), Some("commit message")),
+                Explained::Argument(String::from("file")),
+            ],
+            explained
+        );
+        Ok(())
+    }
+}
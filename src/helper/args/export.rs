@@ -0,0 +1,464 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::helper::args::completions::get_bin_name;
+use crate::helper::args::get_required_help;
+use crate::helper::args::parser::{HelpOption, ParsedHelp};
+use clap::ValueEnum;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Format of the exported CLI specification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SpecFormat {
+    /// [JSON Schema](https://json-schema.org)-like JSON.
+    Json,
+    /// KDL specification for [usage](https://usage.jdx.dev).
+    Usage,
+    /// TypeScript completion specification for [Fig](https://fig.io/docs).
+    Fig,
+}
+
+/// URI of the JSON Schema dialect of the exported JSON.
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Specification of a command (or subcommand).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandSpec {
+    /// Name of the command.
+    pub name: String,
+    /// Description of the command.
+    pub about: Option<String>,
+    /// Usage line.
+    pub usage: Option<String>,
+    /// Options.
+    pub options: Vec<OptionSpec>,
+    /// Subcommands.
+    pub subcommands: Vec<CommandSpec>,
+}
+
+/// Specification of an option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionSpec {
+    /// Flags (e.g. `-f` and `--file`).
+    pub flags: Vec<String>,
+    /// Name of the value.
+    pub value: Option<String>,
+    /// Whether the value is optional.
+    pub optional_value: bool,
+    /// Description.
+    pub description: String,
+}
+
+impl From<&HelpOption> for OptionSpec {
+    fn from(option: &HelpOption) -> Self {
+        Self {
+            flags: option.flags.clone(),
+            value: option
+                .value
+                .as_ref()
+                .map(|value| value.trim_matches(|c| "[]<>=".contains(c)).to_string()),
+            optional_value: option.value.is_some() && !option.takes_value(),
+            description: option.description.to_string(),
+        }
+    }
+}
+
+impl OptionSpec {
+    /// Returns the flag that names the option (the first long flag if any, e.g. `--file`).
+    pub fn name(&self) -> &str {
+        self.flags
+            .iter()
+            .find(|flag| flag.starts_with("--"))
+            .or(self.flags.first())
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
+
+impl CommandSpec {
+    /// Creates the specification from the parsed help.
+    pub fn new(name: &str, about: Option<&str>, help: &ParsedHelp) -> Self {
+        Self {
+            name: name.to_string(),
+            about: about
+                .or(help.about.as_deref())
+                .filter(|about| !about.is_empty())
+                .map(String::from),
+            usage: help.usage.clone(),
+            options: help.options.iter().map(OptionSpec::from).collect(),
+            subcommands: help
+                .subcommands
+                .iter()
+                .map(|subcommand| {
+                    CommandSpec::new(
+                        &subcommand.name,
+                        Some(&subcommand.description),
+                        &subcommand.help.clone().unwrap_or_default(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Renders the specification in the given format.
+    pub fn render(&self, format: SpecFormat) -> Result<String> {
+        match format {
+            SpecFormat::Json => {
+                let mut schema = self.to_json_schema();
+                if let Some(schema) = schema.as_object_mut() {
+                    schema.insert(String::from("$schema"), JSON_SCHEMA_DIALECT.into());
+                }
+                Ok(serde_json::to_string_pretty(&schema)?)
+            }
+            SpecFormat::Usage => {
+                let mut spec = format!(
+                    "name {}\nbin {}\n",
+                    kdl_string(&self.name),
+                    kdl_string(&self.name)
+                );
+                if let Some(ref about) = self.about {
+                    spec += &format!("about {}\n", kdl_string(about));
+                }
+                self.render_kdl_body(&mut spec, 0);
+                Ok(spec)
+            }
+            SpecFormat::Fig => Ok(format!(
+                "const completionSpec: Fig.Spec = {};\nexport default completionSpec;\n",
+                serde_json::to_string_pretty(&self.to_fig())?
+            )),
+        }
+    }
+
+    /// Renders the options and subcommands as KDL nodes.
+    fn render_kdl_body(&self, spec: &mut String, depth: usize) {
+        let indent = "    ".repeat(depth);
+        for option in &self.options {
+            let shorts = option.flags.iter().filter(|f| !f.starts_with("--"));
+            let longs = option.flags.iter().filter(|f| f.starts_with("--"));
+            let mut names = shorts
+                .clone()
+                .take(1)
+                .chain(longs.clone().take(1))
+                .cloned()
+                .collect::<Vec<_>>();
+            if let Some(ref value) = option.value {
+                names.push(if option.optional_value {
+                    format!("[{value}]")
+                } else {
+                    format!("<{value}>")
+                });
+            }
+            spec.push_str(&format!(
+                "{indent}flag {} help={}",
+                kdl_string(&names.join(" ")),
+                kdl_string(&option.description)
+            ));
+            let aliases = shorts.skip(1).chain(longs.skip(1)).collect::<Vec<_>>();
+            if aliases.is_empty() {
+                spec.push('\n');
+            } else {
+                spec.push_str(" {\n");
+                for alias in aliases {
+                    spec.push_str(&format!("{indent}    alias {}\n", kdl_string(alias)));
+                }
+                spec.push_str(&format!("{indent}}}\n"));
+            }
+        }
+        for subcommand in &self.subcommands {
+            spec.push_str(&format!("{indent}cmd {}", kdl_string(&subcommand.name)));
+            if let Some(ref about) = subcommand.about {
+                spec.push_str(&format!(" help={}", kdl_string(about)));
+            }
+            if subcommand.options.is_empty() && subcommand.subcommands.is_empty() {
+                spec.push('\n');
+            } else {
+                spec.push_str(" {\n");
+                subcommand.render_kdl_body(spec, depth + 1);
+                spec.push_str(&format!("{indent}}}\n"));
+            }
+        }
+    }
+
+    /// Converts the specification into a JSON Schema-like object.
+    ///
+    /// The options are the properties keyed by their flag (e.g. `--file`) and the subcommands are
+    /// the nested object properties keyed by their name. The options that appear outside of the
+    /// optional parts of the usage line (e.g. `tool --file <FILE>`) are required.
+    fn to_json_schema(&self) -> serde_json::Value {
+        let mut schema = serde_json::Map::new();
+        schema.insert(String::from("title"), self.name.clone().into());
+        if let Some(ref about) = self.about {
+            schema.insert(String::from("description"), about.clone().into());
+        }
+        if let Some(ref usage) = self.usage {
+            schema.insert(String::from("usage"), usage.clone().into());
+        }
+        schema.insert(String::from("type"), "object".into());
+        let mut properties = serde_json::Map::new();
+        for option in &self.options {
+            let mut property = serde_json::json!({
+                "type": match (&option.value, option.optional_value) {
+                    (None, _) => serde_json::json!("boolean"),
+                    (Some(_), false) => serde_json::json!("string"),
+                    (Some(_), true) => serde_json::json!(["string", "boolean"]),
+                },
+                "description": option.description,
+            });
+            let aliases = option
+                .flags
+                .iter()
+                .map(String::as_str)
+                .filter(|flag| *flag != option.name())
+                .collect::<Vec<_>>();
+            if !aliases.is_empty() {
+                property["aliases"] = aliases.into();
+            }
+            if let Some(ref value) = option.value {
+                property["valueName"] = value.clone().into();
+            }
+            properties.insert(option.name().to_string(), property);
+        }
+        for subcommand in &self.subcommands {
+            properties.insert(subcommand.name.clone(), subcommand.to_json_schema());
+        }
+        schema.insert(String::from("properties"), properties.into());
+        let required_flags = self
+            .usage
+            .as_deref()
+            .map(get_required_flags)
+            .unwrap_or_default();
+        let required = self
+            .options
+            .iter()
+            .filter(|option| {
+                option
+                    .flags
+                    .iter()
+                    .any(|flag| required_flags.contains(flag))
+            })
+            .map(|option| serde_json::Value::from(option.name()))
+            .collect::<Vec<_>>();
+        schema.insert(String::from("required"), required.into());
+        schema.insert(String::from("additionalProperties"), false.into());
+        schema.into()
+    }
+
+    /// Converts the specification into a Fig spec.
+    fn to_fig(&self) -> serde_json::Value {
+        let mut spec = serde_json::Map::new();
+        spec.insert(String::from("name"), self.name.clone().into());
+        if let Some(ref about) = self.about {
+            spec.insert(String::from("description"), about.clone().into());
+        }
+        if !self.options.is_empty() {
+            let options = self
+                .options
+                .iter()
+                .map(|option| {
+                    let mut fig_option = serde_json::json!({
+                        "name": option.flags,
+                        "description": option.description,
+                    });
+                    if let Some(ref value) = option.value {
+                        fig_option["args"] = serde_json::json!({
+                            "name": value,
+                            "isOptional": option.optional_value,
+                        });
+                    }
+                    fig_option
+                })
+                .collect::<Vec<_>>();
+            spec.insert(String::from("options"), options.into());
+        }
+        if !self.subcommands.is_empty() {
+            spec.insert(
+                String::from("subcommands"),
+                self.subcommands
+                    .iter()
+                    .map(CommandSpec::to_fig)
+                    .collect::<Vec<_>>()
+                    .into(),
+            );
+        }
+        spec.into()
+    }
+}
+
+/// Returns the flags that appear outside of the optional (`[...]`) parts of the usage line.
+fn get_required_flags(usage: &str) -> Vec<String> {
+    let mut depth = 0_usize;
+    let mut required = String::new();
+    for c in usage.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => required.push(c),
+            _ => {}
+        }
+    }
+    required
+        .split_whitespace()
+        .filter(|word| word.starts_with('-'))
+        .map(|word| word.split(['=', '<']).next().unwrap_or(word).to_string())
+        .collect()
+}
+
+/// Returns the given text as a quoted KDL string.
+fn kdl_string(text: &str) -> String {
+    format!(
+        "\"{}\"",
+        text.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+/// Exports the parsed help of the given command as a CLI specification.
+pub fn export_spec<Output: Write>(
+    cmd: &str,
+    format: SpecFormat,
+    recursive: bool,
+    out_file: Option<PathBuf>,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let help = get_required_help(cmd, recursive, config)?;
+    let spec = CommandSpec::new(&get_bin_name(cmd), None, &help).render(format)?;
+    match out_file {
+        Some(path) => fs::write(path, spec)?,
+        None => write!(output, "{}", spec)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn get_spec() -> CommandSpec {
+        let mut help = ParsedHelp::parse(
+            r#"Usage: tool [OPTIONS]
+An "internal" tool
+
+Commands:
+  deploy  Deploy the service
+
+Options:
+  -c, --config=FILE       Sets the configuration file
+      --color[=WHEN]      Colorize the output
+  -x, --extract, --get    Extract"#,
+        );
+        help.subcommands[0].help = Some(ParsedHelp::parse("  --dry-run  Do not deploy"));
+        CommandSpec::new("tool", None, &help)
+    }
+
+    #[test]
+    fn test_export_json() -> Result<()> {
+        let spec: serde_json::Value = serde_json::from_str(&get_spec().render(SpecFormat::Json)?)?;
+        assert_eq!(
+            serde_json::json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "tool",
+                "description": "An \"internal\" tool",
+                "usage": "tool [OPTIONS]",
+                "type": "object",
+                "properties": {
+                    "--config": {
+                        "type": "string",
+                        "description": "Sets the configuration file",
+                        "aliases": ["-c"],
+                        "valueName": "FILE"
+                    },
+                    "--color": {
+                        "type": ["string", "boolean"],
+                        "description": "Colorize the output",
+                        "valueName": "WHEN"
+                    },
+                    "--extract": {
+                        "type": "boolean",
+                        "description": "Extract",
+                        "aliases": ["-x", "--get"]
+                    },
+                    "deploy": {
+                        "title": "deploy",
+                        "description": "Deploy the service",
+                        "type": "object",
+                        "properties": {
+                            "--dry-run": {
+                                "type": "boolean",
+                                "description": "Do not deploy"
+                            }
+                        },
+                        "required": [],
+                        "additionalProperties": false
+                    }
+                },
+                "required": [],
+                "additionalProperties": false
+            }),
+            spec
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_required_flags() {
+        assert_eq!(
+            vec!["--name", "-o"],
+            get_required_flags("tool [-v] --name=<NAME> [--color [WHEN]] -o<FILE> <ARG>")
+        );
+        let spec = CommandSpec::new(
+            "tool",
+            None,
+            &ParsedHelp::parse(
+                "Usage: tool [-v] --name <NAME>\n  -n, --name NAME  Name\n  -v  Verbose",
+            ),
+        );
+        assert_eq!(
+            serde_json::json!(["--name"]),
+            spec.to_json_schema()["required"]
+        );
+    }
+
+    #[test]
+    fn test_export_usage() -> Result<()> {
+        assert_eq!(
+            r#"name "tool"
+bin "tool"
+about "An \"internal\" tool"
+flag "-c --config <FILE>" help="Sets the configuration file"
+flag "--color [WHEN]" help="Colorize the output"
+flag "-x --extract" help="Extract" {
+    alias "--get"
+}
+cmd "deploy" help="Deploy the service" {
+    flag "--dry-run" help="Do not deploy"
+}
+"#,
+            get_spec().render(SpecFormat::Usage)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_fig() -> Result<()> {
+        let spec = get_spec().render(SpecFormat::Fig)?;
+        assert!(spec.starts_with("const completionSpec: Fig.Spec = {"));
+        assert!(spec.ends_with("};\nexport default completionSpec;\n"));
+        let json = spec
+            .trim_start_matches("const completionSpec: Fig.Spec = ")
+            .trim_end_matches(";\nexport default completionSpec;\n");
+        let spec: serde_json::Value = serde_json::from_str(json)?;
+        assert_eq!(
+            serde_json::json!({"name": "WHEN", "isOptional": true}),
+            spec["options"][1]["args"]
+        );
+        assert_eq!(
+            serde_json::json!("--dry-run"),
+            spec["subcommands"][0]["options"][0]["name"][0]
+        );
+        Ok(())
+    }
+}
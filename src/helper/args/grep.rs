@@ -0,0 +1,220 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::helper::args::parser::{HelpOption, ParsedHelp};
+use crate::helper::args::{
+    get_parsed_help_recursive, CHECK_EMOTICON, FAIL_EMOTICON, FOUND_EMOTICON, MAX_SUBCOMMAND_DEPTH,
+    OUTPUT_SEPARATOR,
+};
+use colored::*;
+use regex::{Regex, RegexBuilder};
+use std::io::Write;
+
+/// Pattern matcher for the options.
+#[derive(Debug)]
+pub enum Matcher {
+    /// Case-insensitive regular expression.
+    Regex(Regex),
+    /// Case-insensitive fuzzy pattern.
+    ///
+    /// Each word of the pattern should appear (in order) within a single word of the text.
+    Fuzzy(Vec<String>),
+}
+
+impl Matcher {
+    /// Creates a new matcher.
+    pub fn new(pattern: &str, fuzzy: bool) -> Result<Self> {
+        if fuzzy {
+            Ok(Self::Fuzzy(
+                pattern
+                    .split_whitespace()
+                    .map(|word| word.to_lowercase())
+                    .collect(),
+            ))
+        } else {
+            Ok(Self::Regex(
+                RegexBuilder::new(pattern).case_insensitive(true).build()?,
+            ))
+        }
+    }
+
+    /// Returns `true` if the text matches.
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(text),
+            Self::Fuzzy(words) => {
+                let text = text.to_lowercase();
+                words.iter().all(|word| {
+                    text.split(|c: char| c.is_whitespace() || c == ',')
+                        .any(|candidate| is_subsequence(word, candidate))
+                })
+            }
+        }
+    }
+
+    /// Returns `true` if the option matches.
+    pub fn is_option_match(&self, option: &HelpOption) -> bool {
+        self.is_match(&option.spec()) || self.is_match(&option.description)
+    }
+}
+
+/// Returns `true` if the characters of `needle` appear in `haystack` in order.
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
+
+/// Returns the matching options of the command and its (probed) subcommands.
+///
+/// Each entry contains the command (e.g. `git commit`) and the matching options.
+pub fn search_options<'a>(
+    cmd: &str,
+    help: &'a ParsedHelp,
+    matcher: &Matcher,
+) -> Vec<(String, Vec<&'a HelpOption>)> {
+    let mut results = Vec::new();
+    let options = help
+        .options
+        .iter()
+        .filter(|option| matcher.is_option_match(option))
+        .collect::<Vec<_>>();
+    if !options.is_empty() {
+        results.push((cmd.to_string(), options));
+    }
+    for subcommand in &help.subcommands {
+        if let Some(ref help) = subcommand.help {
+            results.extend(search_options(
+                &format!("{} {}", cmd, subcommand.name),
+                help,
+                matcher,
+            ));
+        }
+    }
+    results
+}
+
+/// Searches the options of the given command and prints the matching ones.
+pub fn grep_help<Output: Write>(
+    cmd: &str,
+    pattern: &str,
+    fuzzy: bool,
+    recursive: bool,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let matcher = Matcher::new(pattern, fuzzy)?;
+    writeln!(
+        output,
+        "{}  {} '{}' for '{}'",
+        CHECK_EMOTICON.magenta(),
+        "searching".green().bold(),
+        cmd.white().italic(),
+        pattern.white().italic()
+    )?;
+    let depth = if recursive { MAX_SUBCOMMAND_DEPTH } else { 0 };
+    let help = get_parsed_help_recursive(cmd, config, depth)?;
+    let results = search_options(cmd, &help, &matcher);
+    let count = results
+        .iter()
+        .map(|(_, options)| options.len())
+        .sum::<usize>();
+    if count == 0 {
+        writeln!(
+            output,
+            "{}      {} no matching options found.",
+            FAIL_EMOTICON.magenta(),
+            "fail".red().bold(),
+        )?;
+        return Ok(());
+    }
+    writeln!(
+        output,
+        "{} {} {} matching option(s) found!",
+        FOUND_EMOTICON.magenta(),
+        "success".cyan().bold(),
+        count
+    )?;
+    writeln!(output, "{}", OUTPUT_SEPARATOR.bright_black())?;
+    for (cmd, options) in results {
+        writeln!(output, "{}", cmd.magenta().bold())?;
+        let width = options
+            .iter()
+            .map(|option| option.spec().chars().count())
+            .max()
+            .unwrap_or_default();
+        for option in options {
+            writeln!(
+                output,
+                "  {}  {}",
+                format!("{:width$}", option.spec()).cyan().bold(),
+                option.description
+            )?;
+        }
+    }
+    writeln!(output, "{}", OUTPUT_SEPARATOR.bright_black())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const HELP: &str = r"Usage: curl [options...] <url>
+  -x, --proxy <url>         Use this proxy
+      --proxy-user <user:password>  Proxy user and password
+  -L, --location            Follow redirects
+  -o, --output <file>       Write to file instead of stdout
+
+Commands:
+  get  Download a file";
+
+    fn search(pattern: &str, fuzzy: bool) -> Result<Vec<(String, Vec<String>)>> {
+        let mut help = ParsedHelp::parse(HELP);
+        help.subcommands[0].help = Some(ParsedHelp::parse("  --proxy-header  Proxy header"));
+        let matcher = Matcher::new(pattern, fuzzy)?;
+        Ok(search_options("curl", &help, &matcher)
+            .into_iter()
+            .map(|(cmd, options)| {
+                (
+                    cmd,
+                    options
+                        .iter()
+                        .map(|option| option.flags.join(","))
+                        .collect(),
+                )
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_search_regex() -> Result<()> {
+        assert_eq!(
+            vec![
+                (
+                    String::from("curl"),
+                    vec![String::from("-x,--proxy"), String::from("--proxy-user")]
+                ),
+                (
+                    String::from("curl get"),
+                    vec![String::from("--proxy-header")]
+                ),
+            ],
+            search("proxy", false)?
+        );
+        assert_eq!(
+            vec![(String::from("curl"), vec![String::from("-L,--location")])],
+            search("FOLLOW", false)?
+        );
+        assert!(Matcher::new("(", false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_fuzzy() -> Result<()> {
+        assert_eq!(
+            vec![(String::from("curl"), vec![String::from("-o,--output")])],
+            search("wrt stdut", true)?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,357 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::helper::args::parser::ParsedHelp;
+use crate::helper::args::{
+    get_parsed_help_recursive, CHECK_EMOTICON, FAIL_EMOTICON, FOUND_EMOTICON, MAX_SUBCOMMAND_DEPTH,
+    OUTPUT_SEPARATOR,
+};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+/// File name of the index.
+const INDEX_FILE: &str = "index.json";
+
+/// The only argument that is used for probing the help while indexing.
+///
+/// The other help arguments (e.g. `-h`, `help`) have side effects for some commands
+/// (e.g. `shutdown -h`, `touch help`).
+const INDEX_HELP_ARG: &str = "--help";
+
+/// BM25 term frequency saturation parameter.
+const BM25_K1: f64 = 1.2;
+
+/// BM25 length normalization parameter.
+const BM25_B: f64 = 0.75;
+
+/// Words that are ignored while indexing.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "if", "in", "is", "it", "of",
+    "on", "or", "the", "this", "to", "with",
+];
+
+/// An option in the index.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Command (e.g. `git commit`).
+    pub cmd: String,
+    /// Specification of the option (e.g. `-f, --file ARCHIVE`).
+    pub spec: String,
+    /// Description of the option.
+    pub description: String,
+}
+
+/// Full-text index of the options of the commands.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OptionIndex {
+    /// Indexed options.
+    pub entries: Vec<IndexEntry>,
+    /// Postings of each term, i.e. the indexes of the entries and the term frequencies.
+    postings: BTreeMap<String, Vec<(usize, u32)>>,
+    /// Number of terms in each entry.
+    lengths: Vec<u32>,
+}
+
+impl OptionIndex {
+    /// Returns the default location of the index file.
+    pub fn get_default_location() -> Result<PathBuf> {
+        Ok(Config::get_data_dir()?.join(INDEX_FILE))
+    }
+
+    /// Loads the index from the given file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Saves the index to the given file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Replaces the options of the given command (and its subcommands) in the index.
+    ///
+    /// [`OptionIndex::reindex`] should be called after the commands are added.
+    pub fn add(&mut self, cmd: &str, help: &ParsedHelp) {
+        self.entries
+            .retain(|entry| entry.cmd != cmd && !entry.cmd.starts_with(&format!("{cmd} ")));
+        self.add_entries(cmd, help);
+    }
+
+    /// Adds the options of the given command recursively.
+    fn add_entries(&mut self, cmd: &str, help: &ParsedHelp) {
+        self.entries
+            .extend(help.options.iter().map(|option| IndexEntry {
+                cmd: cmd.to_string(),
+                spec: option.spec(),
+                description: option.description.to_string(),
+            }));
+        for subcommand in &help.subcommands {
+            if let Some(ref help) = subcommand.help {
+                self.add_entries(&format!("{} {}", cmd, subcommand.name), help);
+            }
+        }
+    }
+
+    /// Builds the postings of the terms from the entries.
+    pub fn reindex(&mut self) {
+        self.postings.clear();
+        self.lengths.clear();
+        for (i, entry) in self.entries.iter().enumerate() {
+            let terms = tokenize(&format!("{} {}", entry.spec, entry.description));
+            let mut frequencies = HashMap::<String, u32>::new();
+            for term in &terms {
+                *frequencies.entry(term.to_string()).or_default() += 1;
+            }
+            for (term, frequency) in frequencies {
+                self.postings.entry(term).or_default().push((i, frequency));
+            }
+            self.lengths.push(terms.len() as u32);
+        }
+    }
+
+    /// Searches the index and returns the matching entries ranked by their BM25 scores.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(f64, &IndexEntry)> {
+        let count = self.entries.len() as f64;
+        let average_length =
+            self.lengths.iter().map(|&l| f64::from(l)).sum::<f64>() / count.max(1.);
+        let mut scores = HashMap::<usize, f64>::new();
+        for term in tokenize(query).into_iter().collect::<BTreeSet<_>>() {
+            let Some(postings) = self.postings.get(&term) else {
+                continue;
+            };
+            let frequency = postings.len() as f64;
+            let idf = (1. + (count - frequency + 0.5) / (frequency + 0.5)).ln();
+            for &(i, term_frequency) in postings {
+                let term_frequency = f64::from(term_frequency);
+                let length = f64::from(self.lengths[i]);
+                *scores.entry(i).or_default() += idf * term_frequency * (BM25_K1 + 1.)
+                    / (term_frequency + BM25_K1 * (1. - BM25_B + BM25_B * length / average_length));
+            }
+        }
+        let mut results = scores
+            .into_iter()
+            .map(|(i, score)| (score, &self.entries[i]))
+            .collect::<Vec<_>>();
+        results.sort_by(|a, b| {
+            b.0.total_cmp(&a.0)
+                .then_with(|| a.1.cmd.cmp(&b.1.cmd))
+                .then_with(|| a.1.spec.cmp(&b.1.spec))
+        });
+        results.truncate(limit);
+        results
+    }
+}
+
+/// Splits the given text into lowercase terms.
+///
+/// Stop words are removed and the plural forms are normalized (e.g. `symlinks` -> `symlink`).
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        })
+        .collect()
+}
+
+/// Probes the help of the given commands with [`INDEX_HELP_ARG`] and adds them to the index.
+pub fn build_index<Output: Write>(
+    cmds: &[String],
+    recursive: bool,
+    index_file: Option<PathBuf>,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let index_file = match index_file {
+        Some(path) => path,
+        None => OptionIndex::get_default_location()?,
+    };
+    let mut index = if index_file.exists() {
+        OptionIndex::load(&index_file)?
+    } else {
+        OptionIndex::default()
+    };
+    let config = &Config {
+        check_args: Some(vec![vec![INDEX_HELP_ARG.to_string()]]),
+        ..config.clone()
+    };
+    writeln!(
+        output,
+        "{}  {} {} command(s)",
+        CHECK_EMOTICON.magenta(),
+        "indexing".green().bold(),
+        cmds.len()
+    )?;
+    let depth = if recursive { MAX_SUBCOMMAND_DEPTH } else { 0 };
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = cmds.len().div_ceil(workers).max(1);
+    let results = thread::scope(|s| {
+        let handles = cmds
+            .chunks(chunk_size)
+            .map(|chunk| {
+                s.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|cmd| (cmd, get_parsed_help_recursive(cmd, config, depth)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .map_err(|_| Error::PanicError(String::from("indexing worker")))
+            })
+            .collect::<Result<Vec<_>>>()
+    })?;
+    for (cmd, help) in results.into_iter().flatten() {
+        match help {
+            Ok(help) if !help.options.is_empty() => {
+                writeln!(
+                    output,
+                    "{} {} '{}' indexed ({} options)",
+                    FOUND_EMOTICON.magenta(),
+                    "success".cyan().bold(),
+                    cmd.white().italic(),
+                    help.options.len()
+                )?;
+                index.add(cmd, &help);
+            }
+            Ok(_) => {}
+            Err(e) => writeln!(
+                output,
+                "{}      {} '{}': {}",
+                FAIL_EMOTICON.magenta(),
+                "fail".red().bold(),
+                cmd.white().italic(),
+                e
+            )?,
+        }
+    }
+    index.reindex();
+    writeln!(
+        output,
+        "{} {} {}",
+        FOUND_EMOTICON.magenta(),
+        "writing the index to".green().bold(),
+        format!("{:?}", index_file).white().italic()
+    )?;
+    index.save(&index_file)
+}
+
+/// Searches the index and prints the matching options.
+pub fn search_index<Output: Write>(
+    query: &str,
+    limit: usize,
+    index_file: Option<PathBuf>,
+    output: &mut Output,
+) -> Result<()> {
+    let index_file = match index_file {
+        Some(path) => path,
+        None => OptionIndex::get_default_location()?,
+    };
+    let index = OptionIndex::load(&index_file)?;
+    writeln!(
+        output,
+        "{}  {} the index for '{}'",
+        CHECK_EMOTICON.magenta(),
+        "searching".green().bold(),
+        query.white().italic()
+    )?;
+    let results = index.search(query, limit);
+    if results.is_empty() {
+        writeln!(
+            output,
+            "{}      {} no matching options found.",
+            FAIL_EMOTICON.magenta(),
+            "fail".red().bold(),
+        )?;
+        return Ok(());
+    }
+    let cmd_width = results
+        .iter()
+        .map(|(_, entry)| entry.cmd.chars().count())
+        .max()
+        .unwrap_or_default();
+    let spec_width = results
+        .iter()
+        .map(|(_, entry)| entry.spec.chars().count())
+        .max()
+        .unwrap_or_default();
+    writeln!(output, "{}", OUTPUT_SEPARATOR.bright_black())?;
+    for (_, entry) in results {
+        writeln!(
+            output,
+            "{}  {}  {}",
+            format!("{:cmd_width$}", entry.cmd).magenta().bold(),
+            format!("{:spec_width$}", entry.spec).cyan().bold(),
+            entry.description
+        )?;
+    }
+    writeln!(output, "{}", OUTPUT_SEPARATOR.bright_black())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            vec!["follow", "symlink", "source", "l", "dereference", "class"],
+            tokenize("Follow symlinks in SOURCE (-L, --dereference) class")
+        );
+    }
+
+    #[test]
+    fn test_search_index() -> Result<()> {
+        let mut index = OptionIndex::default();
+        index.add(
+            "cp",
+            &ParsedHelp::parse(
+                "  -L, --dereference  always follow symbolic links in SOURCE\n  \
+                 -v, --verbose  explain what is being done",
+            ),
+        );
+        index.add(
+            "find",
+            &ParsedHelp::parse("  -L  Follow symlinks\n  -P  Never follow symlinks"),
+        );
+        index.add("cp", &ParsedHelp::parse("  -v, --verbose  be verbose"));
+        index.reindex();
+        assert_eq!(3, index.entries.len());
+        let results = index.search("follow symlinks", 10);
+        assert_eq!(
+            vec![("find", "-L"), ("find", "-P")],
+            results
+                .iter()
+                .map(|(_, entry)| (entry.cmd.as_str(), entry.spec.as_str()))
+                .collect::<Vec<_>>()
+        );
+
+        let dir = TestDir::new("index")?;
+        let path = dir.join(INDEX_FILE);
+        index.save(&path)?;
+        let loaded = OptionIndex::load(&path)?;
+        assert_eq!(index.entries, loaded.entries);
+        assert_eq!(1, loaded.search("verbose", 10).len());
+        Ok(())
+    }
+}
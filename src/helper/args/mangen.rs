@@ -0,0 +1,201 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::helper::args::command::build_command;
+use crate::helper::args::completions::get_bin_name;
+use crate::helper::args::parser::{HelpSubcommand, ParsedHelp};
+use crate::helper::args::{get_required_help, FOUND_EMOTICON};
+use clap_mangen::Man;
+use colored::*;
+use roff::{bold, italic, roman, Roff};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Preamble that is prepended to each rendered part by [`Roff`].
+const ROFF_PREAMBLE: &str = ".ie \\n(.g .ds Aq \\(aq\n.el .ds Aq '\n";
+
+/// Renders a man page (in roff format) from the parsed help of a command.
+///
+/// The page contains the `NAME`, `SYNOPSIS`, `OPTIONS` and `COMMANDS` sections.
+pub fn render_man_page(name: &str, section: &str, help: &ParsedHelp) -> Result<String> {
+    let command = build_command(name, help.about.as_deref(), help);
+    let has_options = command.get_arguments().next().is_some();
+    let man = Man::new(command).section(section.to_string());
+    let mut parts = vec![Vec::new(), Vec::new(), Vec::new()];
+    man.render_title(&mut parts[0])?;
+    man.render_name_section(&mut parts[1])?;
+    man.render_synopsis_section(&mut parts[2])?;
+    if has_options {
+        let mut part = Vec::new();
+        man.render_options_section(&mut part)?;
+        parts.push(part);
+    }
+    if !help.subcommands.is_empty() {
+        let mut roff = Roff::default();
+        roff.control("SH", ["COMMANDS"]);
+        for subcommand in &help.subcommands {
+            render_subcommand(&mut roff, subcommand);
+        }
+        parts.push(roff.render().into_bytes());
+    }
+    let mut page = String::from(ROFF_PREAMBLE);
+    for part in parts {
+        let part = String::from_utf8_lossy(&part);
+        page.push_str(part.strip_prefix(ROFF_PREAMBLE).unwrap_or(&part));
+    }
+    Ok(page)
+}
+
+/// Renders the given subcommand (along with its options) as a list item.
+fn render_subcommand(roff: &mut Roff, subcommand: &HelpSubcommand) {
+    roff.control("TP", []);
+    roff.text([bold(subcommand.name.as_str())]);
+    roff.text([roman(subcommand.description.as_str())]);
+    let Some(ref help) = subcommand.help else {
+        return;
+    };
+    if help.options.is_empty() && help.subcommands.is_empty() {
+        return;
+    }
+    roff.control("RS", []);
+    for option in &help.options {
+        roff.control("TP", []);
+        let mut spec = vec![bold(option.flags.join(", "))];
+        if let Some(ref value) = option.value {
+            spec.push(roman(" "));
+            spec.push(italic(value.as_str()));
+        }
+        roff.text(spec);
+        roff.text([roman(option.description.as_str())]);
+    }
+    for subcommand in &help.subcommands {
+        render_subcommand(roff, subcommand);
+    }
+    roff.control("RE", []);
+}
+
+/// Returns the path of the man page in the user manpath (e.g. `~/.local/share/man/man1/cmd.1`).
+///
+/// `man` looks up the pages in the directory of the section number, so the pages of
+/// e.g. section `3p` are placed in `man3`.
+pub fn get_user_man_path(name: &str, section: &str) -> Result<PathBuf> {
+    let number = get_section_number(section)?;
+    dirs::home_dir()
+        .map(|dir| {
+            dir.join(".local")
+                .join("share")
+                .join("man")
+                .join(format!("man{number}"))
+                .join(format!("{name}.{section}"))
+        })
+        .ok_or(Error::HomeDirError)
+}
+
+/// Returns the number of the man page section (e.g. `3` for `3p`).
+///
+/// Returns an error if the section is not a digit followed by optional letters.
+fn get_section_number(section: &str) -> Result<char> {
+    let mut chars = section.chars();
+    match chars.next() {
+        Some(number) if number.is_ascii_digit() && chars.all(|c| c.is_ascii_alphabetic()) => {
+            Ok(number)
+        }
+        _ => Err(Error::ManSectionError(section.to_string())),
+    }
+}
+
+/// Generates a man page for the given command from its help output.
+///
+/// The page is written to `out_file` if given, installed into the user manpath if `install` is
+/// set or written to the output otherwise.
+pub fn generate_man_page<Output: Write>(
+    cmd: &str,
+    section: &str,
+    recursive: bool,
+    out_file: Option<PathBuf>,
+    install: bool,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    get_section_number(section)?;
+    let help = get_required_help(cmd, recursive, config)?;
+    let name = get_bin_name(cmd);
+    let page = render_man_page(&name, section, &help)?;
+    let out_file = match out_file {
+        Some(path) => path,
+        None if install => get_user_man_path(&name, section)?,
+        None => {
+            output.write_all(page.as_bytes())?;
+            return Ok(());
+        }
+    };
+    if let Some(parent) = out_file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&out_file, page)?;
+    writeln!(
+        output,
+        "{} {} {}",
+        FOUND_EMOTICON.magenta(),
+        "man page is written to".green().bold(),
+        format!("{:?}", out_file).white().italic()
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_render_man_page() -> Result<()> {
+        let mut help = ParsedHelp::parse(
+            r"Usage: tool [OPTIONS] <COMMAND>
+An internal tool
+
+Commands:
+  deploy  Deploy the service
+
+Options:
+  -v, --verbose      Enables verbose logging
+  -c, --config FILE  Sets the configuration file",
+        );
+        help.subcommands[0].help = Some(ParsedHelp::parse("  --dry-run  Do not deploy"));
+        let page = render_man_page("tool", "1", &help)?;
+        for expected in [
+            ".TH tool 1",
+            ".SH NAME\ntool \\- An internal tool",
+            ".SH SYNOPSIS",
+            ".SH OPTIONS",
+            "\\fB\\-v\\fR, \\fB\\-\\-verbose\\fR",
+            "Enables verbose logging",
+            ".SH COMMANDS\n.TP\n\\fBdeploy\\fR\nDeploy the service\n.RS\n.TP\n\\fB\\-\\-dry\\-run\\fR",
+        ] {
+            assert!(page.contains(expected), "{expected:?} not found in {page}");
+        }
+        assert_eq!(1, page.matches(ROFF_PREAMBLE).count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_user_man_path() -> Result<()> {
+        let man_dir = dirs::home_dir()
+            .ok_or(Error::HomeDirError)?
+            .join(".local")
+            .join("share")
+            .join("man");
+        assert_eq!(
+            man_dir.join("man1").join("tool.1"),
+            get_user_man_path("tool", "1")?
+        );
+        assert_eq!(
+            man_dir.join("man3").join("tool.3p"),
+            get_user_man_path("tool", "3p")?
+        );
+        for section in ["", "x", "1/../../x", "13", "3p/x"] {
+            assert!(get_user_man_path("tool", section).is_err(), "{section:?}");
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,42 @@
 /// Helper module for Help and Version checks variants.
 pub mod common;
 
+/// Help output parser.
+pub mod parser;
+
+/// Command line explainer.
+pub mod explain;
+
+/// Option search.
+pub mod grep;
+
+/// Full-text index of options.
+pub mod index;
+
+/// Command builder for the parsed help.
+pub mod command;
+
+/// Shell completions generator.
+pub mod completions;
+
+/// Man page generator.
+pub mod mangen;
+
+/// CLI specification exporter.
+pub mod export;
+
 use crate::config::Config;
 use crate::error::{Error, Result};
+use crate::helper::args::parser::ParsedHelp;
 use crate::helper::tty::TtyCommand;
 use colored::*;
-use process_control::{ChildExt, Control};
+use process_control::{ChildExt, Control, Output as ProcessOutput};
 use std::io::Write;
 use std::process::Stdio;
 use std::time::Duration;
 
 /// Emoticon for "checking" message.
-const CHECK_EMOTICON: &str = "(°ロ°)";
+pub const CHECK_EMOTICON: &str = "(°ロ°)";
 /// Emoticon for "found" message.
 pub const FOUND_EMOTICON: &str = "\\(^ヮ^)/";
 /// Emoticon for "fail" message.
@@ -20,6 +45,37 @@ pub const FAIL_EMOTICON: &str = "(×﹏×)";
 const DEBUG_EMOTICON: &str = "(o_O)";
 /// Separator for output.
 const OUTPUT_SEPARATOR: &str = "---";
+/// Maximum depth for probing the help of subcommands.
+pub const MAX_SUBCOMMAND_DEPTH: usize = 2;
+/// Help arguments that are used for probing the help output.
+///
+/// The positional variants (e.g. `help`) are not used since they run the command with an
+/// argument if it is not a subcommand.
+const PROBE_HELP_ARGS: &[&str] = &["--help", "-h"];
+
+/// Runs the given command and returns its output.
+///
+/// Returns an error if the command does not finish within `timeout` seconds.
+fn run_command(command: &str, timeout: u64, stdin: Stdio, stderr: Stdio) -> Result<ProcessOutput> {
+    TtyCommand::new(command)?
+        .env("PAGER", "")
+        .stdin(stdin)
+        .stderr(stderr)
+        .stdout(Stdio::piped())
+        .spawn()?
+        .controlled_with_output()
+        .time_limit(Duration::from_secs(timeout))
+        .terminate_for_timeout()
+        .wait()?
+        .ok_or_else(|| Error::TimeoutError(timeout))
+}
+
+/// Returns the timeout for running the commands.
+pub fn get_timeout(config: &Config) -> u64 {
+    config
+        .timeout
+        .unwrap_or_else(|| Config::default().timeout.unwrap_or_default())
+}
 
 /// Checks if the given arguments exist.
 fn check_args<'a, ArgsIter: Iterator<Item = &'a str>, Output: Write>(
@@ -38,16 +94,7 @@ fn check_args<'a, ArgsIter: Iterator<Item = &'a str>, Output: Write>(
             "checking".green().bold(),
             command.white().italic()
         )?;
-        let cmd_out = TtyCommand::new(&command)?
-            .env("PAGER", "")
-            .stderr(Stdio::inherit())
-            .stdout(Stdio::piped())
-            .spawn()?
-            .controlled_with_output()
-            .time_limit(Duration::from_secs(timeout))
-            .terminate_for_timeout()
-            .wait()?
-            .ok_or_else(|| Error::TimeoutError(timeout))?;
+        let cmd_out = run_command(&command, timeout, Stdio::inherit(), Stdio::inherit())?;
         if cmd_out.status.success() {
             writeln!(
                 output,
@@ -118,9 +165,7 @@ pub fn get_args_help<Output: Write>(
                 cmd,
                 arg_variants.iter().map(|v| v.as_str()),
                 verbose,
-                config
-                    .timeout
-                    .unwrap_or_else(|| Config::default().timeout.unwrap_or_default()),
+                get_timeout(config),
                 output,
             )?;
         }
@@ -128,6 +173,74 @@ pub fn get_args_help<Output: Write>(
     Ok(())
 }
 
+/// Runs the help arguments of the given command and returns the first successful output.
+///
+/// Only the [`PROBE_HELP_ARGS`] are used, in the order of the configured arguments
+/// (all of them if none is configured).
+///
+/// Returns `None` if none of the arguments succeed.
+pub fn probe_help(cmd: &str, config: &Config) -> Result<Option<String>> {
+    let mut args = config
+        .check_args
+        .iter()
+        .flatten()
+        .flatten()
+        .map(String::as_str)
+        .filter(|arg| PROBE_HELP_ARGS.contains(arg))
+        .collect::<Vec<_>>();
+    if args.is_empty() {
+        args = PROBE_HELP_ARGS.to_vec();
+    }
+    for arg in args {
+        let cmd_out = run_command(
+            &format!("{} {}", cmd, arg),
+            get_timeout(config),
+            Stdio::null(),
+            Stdio::null(),
+        )?;
+        if cmd_out.status.success() && !cmd_out.stdout.is_empty() {
+            return Ok(Some(String::from_utf8_lossy(&cmd_out.stdout).to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Probes the help output of the given command and parses it.
+pub fn get_parsed_help(cmd: &str, config: &Config) -> Result<ParsedHelp> {
+    Ok(probe_help(cmd, config)?
+        .map(|help| ParsedHelp::parse(&help))
+        .unwrap_or_default())
+}
+
+/// Probes the help output of the given command (and its subcommands if `recursive` is set).
+///
+/// Returns an error if no options or subcommands are found.
+pub fn get_required_help(cmd: &str, recursive: bool, config: &Config) -> Result<ParsedHelp> {
+    let depth = if recursive { MAX_SUBCOMMAND_DEPTH } else { 0 };
+    let help = get_parsed_help_recursive(cmd, config, depth)?;
+    if help.options.is_empty() && help.subcommands.is_empty() {
+        return Err(Error::HelpNotFoundError(cmd.to_string()));
+    }
+    Ok(help)
+}
+
+/// Probes and parses the help output of the given command along with its subcommands.
+///
+/// Subcommands are probed recursively up to the given depth.
+pub fn get_parsed_help_recursive(cmd: &str, config: &Config, depth: usize) -> Result<ParsedHelp> {
+    let mut help = get_parsed_help(cmd, config)?;
+    if depth > 0 {
+        for subcommand in help.subcommands.iter_mut().filter(|s| s.name != "help") {
+            subcommand.help = Some(get_parsed_help_recursive(
+                &format!("{} {}", cmd, subcommand.name),
+                config,
+                depth - 1,
+            )?);
+        }
+    }
+    Ok(help)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +392,25 @@ halp 0.1.0
         Ok(())
     }
 
+    #[test]
+    fn test_probe_help() -> Result<()> {
+        let config = Config {
+            check_args: Some(vec![
+                vec![String::from("-V")],
+                vec![String::from("help"), String::from("-h")],
+            ]),
+            ..Default::default()
+        };
+        let help = probe_help(&get_test_bin(), &config)?.unwrap_or_default();
+        assert!(help.contains("-h, --help     Print help"), "{help}");
+        let config = Config {
+            check_args: Some(vec![vec![String::from("help")]]),
+            ..Default::default()
+        };
+        assert!(probe_help(&get_test_bin(), &config)?.is_some());
+        Ok(())
+    }
+
     #[test]
     fn test_do_nothing() -> Result<()> {
         let config = Config {
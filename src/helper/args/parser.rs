@@ -0,0 +1,511 @@
+/// Width of a tab character while calculating the indentation.
+const TAB_WIDTH: usize = 8;
+
+/// An option parsed from the help output.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HelpOption {
+    /// Flags of the option (e.g. `-f` and `--file`).
+    pub flags: Vec<String>,
+    /// Name of the value that the option takes.
+    pub value: Option<String>,
+    /// Description of the option.
+    pub description: String,
+}
+
+impl HelpOption {
+    /// Returns `true` if the option requires a value.
+    ///
+    /// Optional values (e.g. `--color[=WHEN]`) are not counted.
+    pub fn takes_value(&self) -> bool {
+        self.value
+            .as_ref()
+            .is_some_and(|value| !value.starts_with('['))
+    }
+
+    /// Returns `true` if the option has the given flag.
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    /// Returns the specification of the option (e.g. `-f, --file ARCHIVE`).
+    pub fn spec(&self) -> String {
+        let flags = self.flags.join(", ");
+        match self.value {
+            Some(ref value) => format!("{flags} {value}"),
+            None => flags,
+        }
+    }
+}
+
+/// A subcommand parsed from the help output.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HelpSubcommand {
+    /// Name of the subcommand.
+    pub name: String,
+    /// Description of the subcommand.
+    pub description: String,
+    /// Parsed help of the subcommand, if it is probed.
+    pub help: Option<ParsedHelp>,
+}
+
+/// Help output of a command, parsed into options and subcommands.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParsedHelp {
+    /// Short description of the command.
+    pub about: Option<String>,
+    /// Usage line.
+    pub usage: Option<String>,
+    /// Options.
+    pub options: Vec<HelpOption>,
+    /// Subcommands.
+    pub subcommands: Vec<HelpSubcommand>,
+}
+
+/// Last parsed entry that the following lines might continue.
+enum Entry {
+    /// Index of the option.
+    Option(usize),
+    /// Index of the subcommand.
+    Subcommand(usize),
+    /// Usage line.
+    Usage,
+}
+
+impl ParsedHelp {
+    /// Parses the given help output.
+    pub fn parse(text: &str) -> Self {
+        let text = sanitize(text);
+        let mut help = ParsedHelp::default();
+        let mut in_commands = false;
+        let mut expect_usage = false;
+        let mut expect_about = true;
+        let mut last: Option<(Entry, usize)> = None;
+        for line in text.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                last = None;
+                continue;
+            }
+            let indent = indentation(line);
+            if expect_usage {
+                help.usage = Some(trimmed.to_string());
+                expect_usage = false;
+                continue;
+            }
+            if trimmed.starts_with('-') {
+                if let Some(option) = parse_option(trimmed) {
+                    expect_about = false;
+                    help.options.push(option);
+                    last = Some((Entry::Option(help.options.len() - 1), indent));
+                    continue;
+                }
+            }
+            if let Some(usage) = strip_prefix_ignore_case(trimmed, "usage:") {
+                if usage.trim().is_empty() {
+                    expect_usage = true;
+                } else if help.usage.is_none() {
+                    help.usage = Some(usage.trim().to_string());
+                    last = Some((Entry::Usage, indent));
+                    continue;
+                }
+                last = None;
+                continue;
+            }
+            if indent == 0 {
+                if let Some(section) = parse_section_header(trimmed) {
+                    expect_about = section == "name";
+                    in_commands = section.contains("command");
+                    expect_usage = section == "synopsis" && help.usage.is_none();
+                    last = None;
+                    continue;
+                }
+                // Subcommands might be introduced by a sentence and grouped under lowercase
+                // headings (e.g. `These are common Git commands used in various situations:`).
+                if trimmed.ends_with(':') && trimmed.to_lowercase().contains("command") {
+                    expect_about = false;
+                    in_commands = true;
+                    last = None;
+                    continue;
+                }
+            }
+            match last {
+                Some((Entry::Option(i), last_indent)) if indent > last_indent => {
+                    append_description(&mut help.options[i].description, trimmed);
+                    continue;
+                }
+                Some((Entry::Subcommand(i), last_indent)) if indent > last_indent => {
+                    append_description(&mut help.subcommands[i].description, trimmed);
+                    continue;
+                }
+                Some((Entry::Usage, last_indent)) if indent > last_indent => {
+                    if let Some(ref mut usage) = help.usage {
+                        append_description(usage, trimmed);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            last = None;
+            if expect_about && !trimmed.starts_with("or:") {
+                let about = trimmed
+                    .split_once(" - ")
+                    .map_or(trimmed, |(_, about)| about);
+                help.about = Some(about.trim().to_string());
+                expect_about = false;
+                continue;
+            }
+            if in_commands && indent > 0 {
+                if let Some(subcommand) = parse_subcommand(trimmed) {
+                    help.subcommands.push(subcommand);
+                    last = Some((Entry::Subcommand(help.subcommands.len() - 1), indent));
+                }
+            }
+        }
+        help
+    }
+
+    /// Returns the option with the given flag.
+    pub fn find_option(&self, flag: &str) -> Option<&HelpOption> {
+        self.options.iter().find(|option| option.has_flag(flag))
+    }
+
+    /// Returns the subcommand with the given name.
+    pub fn find_subcommand(&self, name: &str) -> Option<&HelpSubcommand> {
+        self.subcommands
+            .iter()
+            .find(|subcommand| subcommand.name == name)
+    }
+
+    /// Merges the given help into this one.
+    ///
+    /// Only the options and subcommands that are not known yet are added.
+    pub fn merge(&mut self, other: ParsedHelp) {
+        if self.about.is_none() {
+            self.about = other.about;
+        }
+        if self.usage.is_none() {
+            self.usage = other.usage;
+        }
+        for option in other.options {
+            if !option
+                .flags
+                .iter()
+                .any(|flag| self.find_option(flag).is_some())
+            {
+                self.options.push(option);
+            }
+        }
+        for subcommand in other.subcommands {
+            if self.find_subcommand(&subcommand.name).is_none() {
+                self.subcommands.push(subcommand);
+            }
+        }
+    }
+}
+
+/// Removes the terminal formatting (ANSI escape codes, overstrike sequences and carriage returns)
+/// from the given text.
+pub fn sanitize(text: &str) -> String {
+    let text = console::strip_ansi_codes(text);
+    let mut sanitized = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\u{8}' => {
+                sanitized.pop();
+            }
+            '\r' => {}
+            c => sanitized.push(c),
+        }
+    }
+    sanitized
+}
+
+/// Returns the indentation width of the given line.
+fn indentation(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum()
+}
+
+/// Strips the given prefix from the text while ignoring the case.
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = text.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &text[prefix.len()..])
+}
+
+/// Returns the lowercase name of the section if the given line is a section header.
+///
+/// Both `Options:` (help output) and `OPTIONS` (man page) styles are supported.
+fn parse_section_header(line: &str) -> Option<String> {
+    let name = if let Some(name) = line.strip_suffix(':') {
+        name
+    } else if line.chars().any(|c| c.is_alphabetic()) && !line.chars().any(|c| c.is_lowercase()) {
+        line
+    } else {
+        return None;
+    };
+    (name.split_whitespace().count() <= 4).then(|| name.to_lowercase())
+}
+
+/// Appends the given line to the description.
+fn append_description(description: &mut String, line: &str) {
+    if !description.is_empty() {
+        description.push(' ');
+    }
+    description.push_str(line);
+}
+
+/// Returns `true` if the given string is a valid flag name.
+fn is_flag(flag: &str) -> bool {
+    let name = flag.trim_start_matches('-');
+    let dashes = flag.len() - name.len();
+    (1..=2).contains(&dashes)
+        && name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || "?#@".contains(c))
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_.?#@".contains(c))
+}
+
+/// Splits the given line into the option specification and the description.
+///
+/// The description is expected to be separated by at least two spaces or a tab.
+fn split_description(line: &str) -> (&str, &str) {
+    let bytes = line.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        if b == b'\t' || (b == b' ' && bytes.get(i + 1) == Some(&b' ')) {
+            return (&line[..i], line[i..].trim());
+        }
+    }
+    (line, "")
+}
+
+/// Parses an option line such as `-f, --file=ARCHIVE  use archive file`.
+fn parse_option(line: &str) -> Option<HelpOption> {
+    let (spec, description) = split_description(line);
+    let mut option = HelpOption {
+        description: description.to_string(),
+        ..Default::default()
+    };
+    for token in spec.split([',', ' ']).filter(|token| !token.is_empty()) {
+        if token.starts_with('-') {
+            let (flag, value) = match token.find(['=', '[', '<']) {
+                Some(i) => (&token[..i], Some(&token[i..])),
+                None => (token, None),
+            };
+            if !is_flag(flag) {
+                return None;
+            }
+            option.flags.push(flag.to_string());
+            if let Some(value) = value {
+                let value = value.trim_start_matches('=');
+                let value = if let Some(value) = value.strip_prefix("[=") {
+                    format!("[{value}")
+                } else {
+                    value.to_string()
+                };
+                option.value.get_or_insert(value);
+            }
+        } else if option.flags.is_empty() {
+            return None;
+        } else {
+            match option.value {
+                Some(ref mut value) => {
+                    value.push(' ');
+                    value.push_str(token);
+                }
+                None => option.value = Some(token.to_string()),
+            }
+        }
+    }
+    (!option.flags.is_empty()).then_some(option)
+}
+
+/// Parses a subcommand line such as `build, b    Compile the current package`.
+fn parse_subcommand(line: &str) -> Option<HelpSubcommand> {
+    let (spec, description) = split_description(line);
+    let name = spec.split([',', ' ']).next()?;
+    let is_name = name.chars().next().is_some_and(|c| c.is_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_:.".contains(c));
+    is_name.then(|| HelpSubcommand {
+        name: name.to_string(),
+        description: description.to_string(),
+        help: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn option(flags: &[&str], value: Option<&str>, description: &str) -> HelpOption {
+        HelpOption {
+            flags: flags.iter().map(|flag| flag.to_string()).collect(),
+            value: value.map(String::from),
+            description: description.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_clap_help() {
+        let help = ParsedHelp::parse(
+            r"A CLI tool to get help with CLI tools
+
+Usage: halp [OPTIONS] <CMD>
+
+Commands:
+  plz   Get additional help
+  help  Print this message
+
+Options:
+      --check <ARG>    Sets the argument to check
+  -c, --config <PATH>  Sets the configuration file
+                       [env: HALP_CONFIG=]
+  -h, --help           Print help",
+        );
+        assert_eq!(
+            Some("A CLI tool to get help with CLI tools"),
+            help.about.as_deref()
+        );
+        assert_eq!(Some("halp [OPTIONS] <CMD>"), help.usage.as_deref());
+        assert_eq!(
+            vec![
+                option(&["--check"], Some("<ARG>"), "Sets the argument to check"),
+                option(
+                    &["-c", "--config"],
+                    Some("<PATH>"),
+                    "Sets the configuration file [env: HALP_CONFIG=]"
+                ),
+                option(&["-h", "--help"], None, "Print help"),
+            ],
+            help.options
+        );
+        assert_eq!(
+            vec!["plz", "help"],
+            help.subcommands
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("Get additional help"),
+            help.find_subcommand("plz").map(|s| s.description.as_str())
+        );
+    }
+
+    #[test]
+    fn test_parse_gnu_help() {
+        let help = ParsedHelp::parse(
+            r"Usage: tar [OPTION...] [FILE]...
+  -C, --directory=DIR        change to directory DIR
+      --color[=WHEN]         colorize the output
+  -f, --file=ARCHIVE         use archive file or device ARCHIVE
+  -x, --extract, --get       extract files from an archive
+  -z, --gzip, --gunzip, --ungzip   filter the archive through gzip",
+        );
+        assert_eq!(None, help.about);
+        assert_eq!(5, help.options.len());
+        let directory = help.find_option("--directory").expect("option not found");
+        assert_eq!(Some("DIR"), directory.value.as_deref());
+        assert!(directory.takes_value());
+        let color = help.find_option("--color").expect("option not found");
+        assert_eq!(Some("[WHEN]"), color.value.as_deref());
+        assert!(!color.takes_value());
+        assert_eq!(
+            Some("extract files from an archive"),
+            help.find_option("--get").map(|o| o.description.as_str())
+        );
+        assert!(help.find_option("-z").is_some_and(|o| !o.takes_value()));
+    }
+
+    #[test]
+    fn test_parse_man_page() {
+        let help = ParsedHelp::parse(
+            "NAME\n       ls - list directory contents\n\nOPTIONS\n       \
+             -a, --all\n              do not ignore entries starting with .\n\n       \
+             -w, --width=COLS\n              set output width to COLS.  0 means no limit\n",
+        );
+        assert_eq!(Some("list directory contents"), help.about.as_deref());
+        assert_eq!(
+            vec![
+                option(
+                    &["-a", "--all"],
+                    None,
+                    "do not ignore entries starting with ."
+                ),
+                option(
+                    &["-w", "--width"],
+                    Some("COLS"),
+                    "set output width to COLS.  0 means no limit"
+                ),
+            ],
+            help.options
+        );
+    }
+
+    #[test]
+    fn test_parse_grouped_subcommands() {
+        let help = ParsedHelp::parse(
+            r"usage: git [-v | --version] [-h | --help] [-C <path>]
+           <command> [<args>]
+
+These are common Git commands used in various situations:
+
+start a working area (see also: git help tutorial)
+   clone     Clone a repository into a new directory
+   init      Create an empty Git repository or reinitialize an existing one
+
+grow, mark and tweak your common history
+   branch    List, create, or delete branches
+   commit    Record changes to the repository
+
+'git help -a' and 'git help -g' list available subcommands and some
+concept guides. See 'git help <command>' or 'git help <concept>'.",
+        );
+        assert_eq!(None, help.about);
+        assert_eq!(
+            Some("git [-v | --version] [-h | --help] [-C <path>] <command> [<args>]"),
+            help.usage.as_deref()
+        );
+        assert_eq!(
+            vec!["clone", "init", "branch", "commit"],
+            help.subcommands
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("Record changes to the repository"),
+            help.find_subcommand("commit")
+                .map(|s| s.description.as_str())
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut help = ParsedHelp::parse("  -a, --all  all");
+        help.merge(ParsedHelp::parse("  -a  everything\n  -b  brief"));
+        assert_eq!(
+            vec!["all", "brief"],
+            help.options
+                .iter()
+                .map(|o| o.description.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(
+            "NAME bold",
+            sanitize("N\u{8}NA\u{8}AM\u{8}ME\u{8}E \x1b[1mbold\x1b[0m\r")
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::config::Config;
+use crate::error::Result;
+use crate::helper::docs::ContentFormat;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default time-to-live of the cached pages (1 day).
+pub const DEFAULT_CACHE_TTL: u64 = 60 * 60 * 24;
+
+/// A cached page.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// Contents of the page.
+    pub content: String,
+    /// Format of the page.
+    pub format: ContentFormat,
+    /// `ETag` header of the response.
+    pub etag: Option<String>,
+    /// `Last-Modified` header of the response.
+    pub last_modified: Option<String>,
+    /// Time of the last fetch or revalidation (seconds since the Unix epoch).
+    pub fetched_at: u64,
+}
+
+impl CacheEntry {
+    /// Returns the age of the entry.
+    pub fn age(&self) -> Duration {
+        now().saturating_sub(Duration::from_secs(self.fetched_at))
+    }
+}
+
+/// On-disk cache of the provider pages.
+///
+/// Pages are stored per provider and URL of the page
+/// (e.g. `<cache_dir>/halp/pages/cheat.sh/https%3A%2F%2Fcheat.sh%2Ftar.json`), so the pages of a
+/// provider are not mixed up when its URL (or e.g. the cheat.sh options) changes.
+#[derive(Clone, Debug)]
+pub struct PageCache {
+    /// Directory of the cache.
+    dir: PathBuf,
+    /// Time-to-live of the entries.
+    ttl: Duration,
+}
+
+impl PageCache {
+    /// Creates a new cache in the given directory.
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        Self { dir, ttl }
+    }
+
+    /// Creates the cache in the default location (e.g. `~/.cache/halp/pages`).
+    ///
+    /// Returns `None` if the cache directory cannot be determined.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let dir = dirs::cache_dir()?
+            .join(env!("CARGO_PKG_NAME"))
+            .join("pages");
+        Some(Self::new(
+            dir,
+            Duration::from_secs(config.cache_ttl.unwrap_or(DEFAULT_CACHE_TTL)),
+        ))
+    }
+
+    /// Returns the path of the entry for the given provider and key.
+    fn get_path(&self, provider: &str, key: &str) -> PathBuf {
+        self.dir
+            .join(encode_key(provider))
+            .join(format!("{}.json", encode_key(key)))
+    }
+
+    /// Returns the cached entry.
+    pub fn get(&self, provider: &str, key: &str) -> Option<CacheEntry> {
+        let contents = fs::read_to_string(self.get_path(provider, key)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Returns `true` if the entry is not expired.
+    pub fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        entry.age() < self.ttl
+    }
+
+    /// Stores the entry.
+    pub fn set(&self, provider: &str, key: &str, entry: &CacheEntry) -> Result<()> {
+        let path = self.get_path(provider, key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+}
+
+/// Returns the current time as the duration since the Unix epoch.
+pub fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Encodes the key so that it can be used as a file name.
+fn encode_key(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "-_.".contains(c) {
+                c.to_string()
+            } else {
+                let mut buffer = [0; 4];
+                c.encode_utf8(&mut buffer)
+                    .bytes()
+                    .map(|b| format!("%{b:02X}"))
+                    .collect()
+            }
+        })
+        .collect::<String>()
+        .replace("..", "%2E%2E")
+}
+
+/// Formats the age in a human-readable way (e.g. `3 days`).
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    let (value, unit) = match secs {
+        0..=59 => return String::from("less than a minute"),
+        60..=3599 => (secs / 60, "minute"),
+        3600..=86399 => (secs / 3600, "hour"),
+        _ => (secs / 86400, "day"),
+    };
+    format!("{value} {unit}{}", if value == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_page_cache() -> Result<()> {
+        let dir = TestDir::new("cache")?;
+        let cache = PageCache::new(dir.to_path_buf(), Duration::from_secs(60));
+        assert_eq!(None, cache.get("cheat.sh", "git/commit"));
+        let mut entry = CacheEntry {
+            content: String::from("# git commit"),
+            format: ContentFormat::Plain,
+            etag: Some(String::from("\"abc\"")),
+            last_modified: None,
+            fetched_at: now().as_secs(),
+        };
+        cache.set("cheat.sh", "git/commit", &entry)?;
+        assert!(dir.join("cheat.sh").join("git%2Fcommit.json").exists());
+        assert_eq!(Some(&entry), cache.get("cheat.sh", "git/commit").as_ref());
+        assert!(cache.is_fresh(&entry));
+        entry.fetched_at -= 120;
+        assert!(!cache.is_fresh(&entry));
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_key() {
+        assert_eq!("tar", encode_key("tar"));
+        assert_eq!("pages%2Fcommon%2Ftar.md", encode_key("pages/common/tar.md"));
+        assert_eq!("%2E%2E%2Fx", encode_key("../x"));
+        assert_eq!("%C3%BC", encode_key("ü"));
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!("less than a minute", format_age(Duration::from_secs(5)));
+        assert_eq!("1 minute", format_age(Duration::from_secs(60)));
+        assert_eq!("5 hours", format_age(Duration::from_secs(5 * 3600 + 10)));
+        assert_eq!("3 days", format_age(Duration::from_secs(3 * 86400)));
+    }
+}
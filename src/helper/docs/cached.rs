@@ -0,0 +1,97 @@
+use crate::error::Result;
+use crate::helper::docs::{HelpPage, HelpProvider};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Provider that keeps the fetched pages of another provider in memory.
+///
+/// This avoids fetching the same page again when it is selected multiple times from the menu.
+/// Errors are not cached.
+pub struct CachedProvider {
+    /// Inner provider.
+    provider: Box<dyn HelpProvider>,
+    /// Fetched pages.
+    pages: Mutex<HashMap<String, HelpPage>>,
+}
+
+impl CachedProvider {
+    /// Wraps the given provider.
+    pub fn new(provider: Box<dyn HelpProvider>) -> Self {
+        Self {
+            provider,
+            pages: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl HelpProvider for CachedProvider {
+    fn name(&self) -> &str {
+        self.provider.name()
+    }
+
+    fn title(&self) -> String {
+        self.provider.title()
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        if let Some(page) = self
+            .pages
+            .lock()
+            .ok()
+            .and_then(|pages| pages.get(query).cloned())
+        {
+            return Ok(page);
+        }
+        let page = self.provider.fetch(query)?;
+        if let Ok(mut pages) = self.pages.lock() {
+            pages.insert(query.to_string(), page.clone());
+        }
+        Ok(page)
+    }
+
+    fn variants(&self, query: &str) -> Result<Vec<String>> {
+        self.provider.variants(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CountingProvider(Arc<AtomicUsize>);
+
+    impl HelpProvider for CountingProvider {
+        fn name(&self) -> &str {
+            "counter"
+        }
+
+        fn fetch(&self, query: &str) -> Result<HelpPage> {
+            let count = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            if query == "unknown" {
+                return Err(Error::NotFoundError(String::from("Unknown topic.")));
+            }
+            Ok(HelpPage {
+                provider: self.name().to_string(),
+                content: format!("{query} #{count}"),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn test_cached_provider() -> Result<()> {
+        let count = Arc::new(AtomicUsize::new(0));
+        let provider = CachedProvider::new(Box::new(CountingProvider(count.clone())));
+        assert_eq!("ls #1", provider.fetch("ls")?.content);
+        assert_eq!("ls #1", provider.fetch("ls")?.content);
+        assert_eq!("tar #2", provider.fetch("tar")?.content);
+        assert!(provider.fetch("unknown").is_err());
+        assert!(provider.fetch("unknown").is_err());
+        assert_eq!(4, count.load(Ordering::SeqCst));
+        Ok(())
+    }
+}
@@ -0,0 +1,349 @@
+use crate::config::CheatPath;
+use crate::error::{Error, Result};
+use crate::helper::args::FOUND_EMOTICON;
+use crate::helper::docs::file::is_inner_path;
+use crate::helper::docs::{HelpPage, HelpProvider};
+use colored::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the provider.
+pub const CHEAT_PROVIDER: &str = "cheat";
+
+/// A cheat sheet in a local directory.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CheatSheet {
+    /// Topic of the sheet (e.g. `kubectl/rollout`).
+    pub topic: String,
+    /// Name of the cheat path that the sheet is found in.
+    pub cheatpath: String,
+    /// Path of the sheet.
+    pub path: PathBuf,
+    /// Tags of the sheet and its cheat path.
+    pub tags: Vec<String>,
+    /// Syntax of the sheet (e.g. `bash`).
+    pub syntax: Option<String>,
+    /// Contents of the sheet without the front matter.
+    pub content: String,
+}
+
+/// Provider for [`cheat`](https://github.com/cheat/cheat)-compatible sheets in local directories.
+///
+/// The cheat paths are searched in the given order, so a sheet in the first path (e.g. the
+/// personal directory) takes precedence over the same sheet in the following paths (e.g. a team
+/// checkout). Nested topics are stored in subdirectories (e.g. `<path>/kubectl/rollout`).
+#[derive(Clone, Debug)]
+pub struct CheatProvider {
+    /// Cheat paths in the order of precedence.
+    paths: Vec<CheatPath>,
+}
+
+impl CheatProvider {
+    /// Creates a new provider for the given cheat paths.
+    pub fn new(paths: Vec<CheatPath>) -> Self {
+        Self { paths }
+    }
+
+    /// Returns the sheet for the given topic.
+    ///
+    /// Words in the topic are treated as nested topics (e.g. `kubectl rollout`).
+    pub fn find_sheet(&self, topic: &str) -> Result<Option<CheatSheet>> {
+        let topic = normalize_topic(topic);
+        if topic.split('/').any(|part| part == ".." || part.is_empty()) {
+            return Ok(None);
+        }
+        for cheatpath in &self.paths {
+            let path = cheatpath.path.join(&topic);
+            if path.is_file() {
+                return read_sheet(cheatpath, &topic, &path).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the sheets in all cheat paths, optionally filtered by the given tag.
+    ///
+    /// Sheets that are shadowed by a sheet with the same topic in a preceding path are omitted,
+    /// and so are the files that are not valid UTF-8.
+    pub fn list_sheets(&self, tag: Option<&str>) -> Result<Vec<CheatSheet>> {
+        let mut sheets = BTreeMap::new();
+        for cheatpath in &self.paths {
+            let mut paths = Vec::new();
+            find_files(&cheatpath.path, &mut paths)?;
+            for path in paths {
+                let Some(topic) = path
+                    .strip_prefix(&cheatpath.path)
+                    .ok()
+                    .and_then(|topic| topic.to_str())
+                    .map(|topic| topic.replace('\\', "/"))
+                else {
+                    continue;
+                };
+                if sheets.contains_key(&topic) {
+                    continue;
+                }
+                let sheet = match read_sheet(cheatpath, &topic, &path) {
+                    Ok(sheet) => sheet,
+                    // Skip the files that are not text (e.g. images next to the sheets).
+                    Err(Error::IoError(e)) if e.kind() == io::ErrorKind::InvalidData => continue,
+                    Err(e) => return Err(e),
+                };
+                sheets.insert(topic, sheet);
+            }
+        }
+        Ok(sheets
+            .into_values()
+            .filter(|sheet| tag.map_or(true, |tag| sheet.tags.iter().any(|t| t == tag)))
+            .collect())
+    }
+
+    /// Returns the nested topics of the given topic (e.g. `rollout` for `kubectl`).
+    ///
+    /// Only the directories of the topic (e.g. `<path>/kubectl/`) are searched.
+    fn find_subtopics(&self, topic: &str) -> Result<Vec<String>> {
+        let topic = normalize_topic(topic);
+        if topic.is_empty() || !is_inner_path(&topic) {
+            return Ok(Vec::new());
+        }
+        let mut subtopics = BTreeSet::new();
+        for cheatpath in &self.paths {
+            let dir = cheatpath.path.join(&topic);
+            let mut paths = Vec::new();
+            find_files(&dir, &mut paths)?;
+            subtopics.extend(paths.iter().filter_map(|path| {
+                path.strip_prefix(&dir)
+                    .ok()
+                    .and_then(|subtopic| subtopic.to_str())
+                    .map(|subtopic| subtopic.replace('\\', "/"))
+            }));
+        }
+        Ok(subtopics.into_iter().collect())
+    }
+}
+
+impl HelpProvider for CheatProvider {
+    fn name(&self) -> &str {
+        CHEAT_PROVIDER
+    }
+
+    fn title(&self) -> String {
+        String::from("Show the local cheat sheet")
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        let content = match self.find_sheet(query)? {
+            Some(sheet) => sheet.content,
+            None => {
+                let subtopics = self.find_subtopics(query)?;
+                if subtopics.is_empty() {
+                    return Err(Error::NotFoundError(format!(
+                        "No cheat sheet for `{query}`"
+                    )));
+                }
+                format!(
+                    "# {query}: nested topics\n\n{}",
+                    subtopics
+                        .iter()
+                        .map(|topic| format!("- {topic}"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                )
+            }
+        };
+        Ok(HelpPage {
+            provider: CHEAT_PROVIDER.to_string(),
+            content,
+            ..Default::default()
+        })
+    }
+}
+
+/// Converts the query into a topic path (e.g. `kubectl rollout` to `kubectl/rollout`).
+fn normalize_topic(topic: &str) -> String {
+    topic.split_whitespace().collect::<Vec<_>>().join("/")
+}
+
+/// Collects the files in the directory recursively, skipping the hidden files (e.g. `.git`).
+fn find_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map_or(true, |name| name.starts_with('.'))
+        {
+            continue;
+        }
+        if path.is_dir() {
+            find_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Reads the sheet and parses its front matter.
+fn read_sheet(cheatpath: &CheatPath, topic: &str, path: &Path) -> Result<CheatSheet> {
+    let contents = fs::read_to_string(path)?;
+    let (front_matter, content) = split_front_matter(&contents);
+    let mut sheet = CheatSheet {
+        topic: topic.to_string(),
+        cheatpath: cheatpath.name.to_string(),
+        path: path.to_path_buf(),
+        tags: cheatpath.tags.clone(),
+        content: content.to_string(),
+        ..Default::default()
+    };
+    let mut in_tags = false;
+    for line in front_matter.lines() {
+        if let Some(tag) = line.trim().strip_prefix("- ").filter(|_| in_tags) {
+            sheet.tags.push(tag.trim().to_string());
+            continue;
+        }
+        in_tags = false;
+        match line.split_once(':') {
+            Some(("syntax", value)) => sheet.syntax = Some(value.trim().to_string()),
+            Some(("tags", value)) => {
+                let value = value.trim();
+                in_tags = value.is_empty();
+                sheet.tags.extend(
+                    value
+                        .trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .split(',')
+                        .map(|tag| tag.trim().to_string())
+                        .filter(|tag| !tag.is_empty()),
+                );
+            }
+            _ => {}
+        }
+    }
+    sheet.tags.sort();
+    sheet.tags.dedup();
+    Ok(sheet)
+}
+
+/// Splits the YAML front matter (delimited by `---` lines) from the contents of the sheet.
+fn split_front_matter(contents: &str) -> (&str, &str) {
+    let Some(rest) = contents
+        .strip_prefix("---\n")
+        .or_else(|| contents.strip_prefix("---\r\n"))
+    else {
+        return ("", contents);
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let content = &rest[end + 4..];
+            (
+                &rest[..end],
+                content.trim_start_matches('\r').trim_start_matches('\n'),
+            )
+        }
+        None => ("", contents),
+    }
+}
+
+/// Lists the local cheat sheets, optionally filtered by the given tag.
+pub fn list_cheat_sheets<Output: Write>(
+    paths: &[CheatPath],
+    tag: Option<&str>,
+    output: &mut Output,
+) -> Result<()> {
+    for sheet in CheatProvider::new(paths.to_vec()).list_sheets(tag)? {
+        writeln!(
+            output,
+            "{} {}  {}  {}",
+            FOUND_EMOTICON.magenta(),
+            sheet.topic.green().bold(),
+            sheet.cheatpath.cyan(),
+            sheet.tags.join(",").white().italic()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_cheat_provider() -> Result<()> {
+        let dir = TestDir::new("cheat")?;
+        let personal = dir.join("personal");
+        let team = dir.join("team");
+        fs::create_dir_all(team.join("kubectl"))?;
+        fs::create_dir_all(team.join(".git"))?;
+        fs::create_dir_all(personal.join("kubectl"))?;
+        fs::write(personal.join("tar"), "# my tar")?;
+        fs::write(personal.join("kubectl").join("logs"), "# kubectl logs")?;
+        fs::write(team.join("tar"), "# team tar")?;
+        fs::write(team.join(".git").join("config"), "")?;
+        fs::write(team.join("logo.png"), [0x89, b'P', b'N', b'G', 0xff])?;
+        fs::write(
+            team.join("kubectl").join("rollout"),
+            "---\nsyntax: bash\ntags: [ k8s, deploy ]\n---\n# kubectl rollout\n",
+        )?;
+        let provider = CheatProvider::new(vec![
+            CheatPath {
+                name: String::from("personal"),
+                path: personal,
+                tags: Vec::new(),
+            },
+            CheatPath {
+                name: String::from("team"),
+                path: team.clone(),
+                tags: vec![String::from("team")],
+            },
+        ]);
+        assert_eq!("# my tar", provider.fetch("tar")?.content);
+        let sheet = provider.find_sheet("kubectl rollout")?;
+        assert_eq!(
+            Some(CheatSheet {
+                topic: String::from("kubectl/rollout"),
+                cheatpath: String::from("team"),
+                path: team.join("kubectl").join("rollout"),
+                tags: vec![
+                    String::from("deploy"),
+                    String::from("k8s"),
+                    String::from("team")
+                ],
+                syntax: Some(String::from("bash")),
+                content: String::from("# kubectl rollout\n"),
+            }),
+            sheet
+        );
+        assert_eq!(
+            "# kubectl: nested topics\n\n- logs\n- rollout",
+            provider.fetch("kubectl")?.content
+        );
+        assert_eq!(
+            vec!["kubectl/logs", "kubectl/rollout", "tar"],
+            provider
+                .list_sheets(None)?
+                .iter()
+                .map(|sheet| sheet.topic.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(1, provider.list_sheets(Some("k8s"))?.len());
+        assert!(provider.fetch("../personal/tar").is_err());
+        assert!(provider.fetch("ls").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_front_matter() {
+        assert_eq!(("", "# tar"), split_front_matter("# tar"));
+        assert_eq!(
+            ("tags:\n  - vcs", "# git"),
+            split_front_matter("---\ntags:\n  - vcs\n---\n# git")
+        );
+    }
+}
@@ -1,6 +1,14 @@
-use crate::error::{Error, Result};
-use crate::helper::docs::HelpProvider;
-use ureq::Agent;
+use crate::config::Config;
+use crate::error::Result;
+use crate::helper::args::get_timeout;
+use crate::helper::docs::file::FileProvider;
+use crate::helper::docs::http::{percent_encode, HttpProvider};
+use crate::helper::docs::{HelpPage, HelpProvider};
+use regex::Regex;
+use std::path::PathBuf;
+
+/// Name of the cheat.sh provider.
+pub const CHEAT_SH_PROVIDER: &str = "cheat.sh";
 
 /// Default cheat sheet provider URL.
 pub const DEFAULT_CHEAT_SHEET_PROVIDER: &str = "https://cheat.sh";
@@ -10,44 +18,131 @@ pub const DEFAULT_CHEAT_SHEET_PROVIDER: &str = "https://cheat.sh";
 /// See <https://github.com/chubin/cheat.sh/blob/83bffa587b6c1048cbcc40ea8fa6af675203fd5f/bin/app.py#L76>
 const CHEAT_SHEET_USER_AGENT: &str = "fetch";
 
-/// The `cheat.sh` provider
-pub struct CheatDotSh;
+/// Creates the `cheat.sh` provider with the given URL (defaults to [`DEFAULT_CHEAT_SHEET_PROVIDER`]).
+pub fn provider(url: Option<&str>) -> HttpProvider {
+    let provider = HttpProvider::new(
+        CHEAT_SH_PROVIDER,
+        url.unwrap_or(DEFAULT_CHEAT_SHEET_PROVIDER),
+    )
+    .user_agent(CHEAT_SHEET_USER_AGENT)
+    .raw_query();
+    match Regex::new(r"\AUnknown topic\.") {
+        Ok(not_found) => provider.not_found(not_found),
+        Err(_) => provider,
+    }
+}
+
+/// Returns the query for searching the pages of the topic for the keyword (e.g. `tar/~extract`).
+pub fn search_query(topic: &str, keyword: &str) -> String {
+    format!("{}/~{keyword}", topic.trim_end_matches('/'))
+}
+
+/// Returns the query for listing the topics in the namespace (e.g. `rust/:list`).
+///
+/// All topics are listed if the namespace is empty.
+pub fn list_query(namespace: &str) -> String {
+    match namespace.trim_end_matches('/') {
+        "" => String::from(":list"),
+        namespace => format!("{namespace}/:list"),
+    }
+}
+
+/// The `cheat.sh` provider with support for its query language.
+///
+/// The query is passed to cheat.sh as is, so all of the following work:
+///
+/// - `tar`: the page of a command.
+/// - `git/commit`, `rust/closures`: subtopics and programming language namespaces.
+/// - `tar/~extract`: keyword search (see [`search_query`]).
+/// - `:list`, `rust/:list`: list of the topics (see [`list_query`]).
+/// - `python/reverse list`: questions, the words are joined with `+`.
+///
+/// The configured options (e.g. `T` for no colors, `Q` for no comments) are appended to the
+/// options in the query (e.g. `tar?Q`).
+///
+/// The pages in the mirror are synchronized with the configured options, so the mirror is only
+/// used for the queries without options.
+#[derive(Clone, Debug)]
+pub struct CheatSh {
+    /// Provider for fetching the pages.
+    pub http: HttpProvider,
+    /// Options to append to the queries (e.g. `TQ`).
+    pub options: Option<String>,
+    /// Local mirror of the pages.
+    pub mirror: Option<PathBuf>,
+}
+
+impl CheatSh {
+    /// Creates a new provider with the given URL (defaults to [`DEFAULT_CHEAT_SHEET_PROVIDER`]).
+    pub fn new(url: Option<&str>) -> Self {
+        Self {
+            http: provider(url),
+            options: None,
+            mirror: None,
+        }
+    }
+
+    /// Creates a new provider from the configuration.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            http: provider(config.cheat_sh_url.as_deref()).timeout(get_timeout(config)),
+            options: config.cheat_sh_options.clone(),
+            mirror: None,
+        }
+    }
 
-impl HelpProvider for CheatDotSh {
-    fn url(&self) -> &'static str {
-        DEFAULT_CHEAT_SHEET_PROVIDER
+    /// Returns the query that is sent to cheat.sh.
+    ///
+    /// The characters that are not allowed in the path (e.g. `#` and `%`) are percent-encoded,
+    /// while the separators of the query language (e.g. `/`, `~` and `:`) are kept.
+    pub fn get_query(&self, query: &str) -> String {
+        let (path, options) = query.split_once('?').unwrap_or((query, ""));
+        let path = percent_encode(
+            &path.split_whitespace().collect::<Vec<_>>().join("+"),
+            b"!$&'()*+,;=:@/",
+        );
+        let options = percent_encode(options, b"");
+        let mut options = options.to_string();
+        for option in self.options.iter().flat_map(|options| options.chars()) {
+            if !options.contains(option) {
+                options.push(option);
+            }
+        }
+        if options.is_empty() {
+            path
+        } else {
+            format!("{path}?{options}")
+        }
     }
+}
 
-    fn build_request(
-        &self,
-        cmd: &str,
-        url: &str,
-    ) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
-        let agent: Agent = Agent::config_builder()
-            .user_agent(CHEAT_SHEET_USER_AGENT)
-            .build()
-            .into();
-        agent.get(&format!("{}/{}", url, cmd))
+impl HelpProvider for CheatSh {
+    fn name(&self) -> &str {
+        CHEAT_SH_PROVIDER
     }
 
-    fn fetch(&self, cmd: &str, custom_url: &Option<String>) -> Result<String> {
-        let response = self._fetch(cmd, custom_url);
-        if let Ok(page) = &response {
-            if page.starts_with("Unknown topic.") {
-                return Err(Error::ProviderError(page.to_owned()));
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        if let Some(mirror) = self.mirror.as_ref().filter(|_| !query.contains('?')) {
+            let mirror = FileProvider::new(CHEAT_SH_PROVIDER, mirror.to_path_buf());
+            if mirror.find_page(query.trim()).is_some() {
+                return mirror.fetch(query.trim());
             }
         }
-        response
+        self.http.fetch(&self.get_query(query))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::Result;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+    use std::fs;
 
     #[test]
     fn test_fetch_cheat_sheet() -> Result<()> {
-        let output = CheatDotSh.fetch("ls", &None)?;
+        let output = provider(None).fetch("ls")?.content;
         assert!(output.contains(
             "# To display all files, along with the size (with unit suffixes) and timestamp:"
         ));
@@ -56,4 +151,41 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[test]
+    fn test_query() {
+        let mut cheat_sh = CheatSh::new(None);
+        assert_eq!("git/commit", cheat_sh.get_query("git/commit"));
+        assert_eq!(
+            "python/reverse+list",
+            cheat_sh.get_query("python/reverse list")
+        );
+        assert_eq!("c%23/100%25", cheat_sh.get_query("c#/100%"));
+        assert_eq!("tar/~extract", search_query("tar", "extract"));
+        assert_eq!(":list", list_query(""));
+        assert_eq!("rust/:list", list_query("rust/"));
+        cheat_sh.options = Some(String::from("TQ"));
+        assert_eq!("tar?TQ", cheat_sh.get_query("tar"));
+        assert_eq!("tar/~extract?QT", cheat_sh.get_query("tar/~extract?Q"));
+        assert_eq!(
+            "https://cheat.sh/c%23/:list?TQ",
+            cheat_sh
+                .http
+                .get_url(&cheat_sh.get_query(&list_query("c#")))
+        );
+    }
+
+    #[test]
+    fn test_mirror() -> Result<()> {
+        let dir = TestDir::new("cheat-sh-mirror")?;
+        fs::write(dir.join("tar"), "tar page")?;
+        // Nothing listens on this port so the requests fail.
+        let cheat_sh = CheatSh {
+            mirror: Some(dir.to_path_buf()),
+            ..CheatSh::new(Some("http://127.0.0.1:1"))
+        };
+        assert_eq!("tar page", cheat_sh.fetch("tar")?.content);
+        assert!(cheat_sh.fetch("tar?Q").is_err());
+        Ok(())
+    }
 }
@@ -1,36 +1,23 @@
-use crate::helper::docs::HelpProvider;
-use ureq::Agent;
+use crate::helper::docs::http::HttpProvider;
 
 /// The default cheatsheets provider URL.
 pub const DEFAULT_CHEATSHEETS_PROVIDER: &str =
     "https://raw.githubusercontent.com/cheat/cheatsheets/master";
 
-/// The `cheatsheets` provider
-pub struct Cheatsheets;
-
-impl HelpProvider for Cheatsheets {
-    fn url(&self) -> &'static str {
-        DEFAULT_CHEATSHEETS_PROVIDER
-    }
-
-    fn build_request(
-        &self,
-        cmd: &str,
-        url: &str,
-    ) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
-        let agent: Agent = Agent::config_builder().build().into();
-        agent.get(&format!("{}/{}", url, cmd))
-    }
+/// Creates the `cheatsheets` provider with the given URL (defaults to [`DEFAULT_CHEATSHEETS_PROVIDER`]).
+pub fn provider(url: Option<&str>) -> HttpProvider {
+    HttpProvider::new("cheatsheets", url.unwrap_or(DEFAULT_CHEATSHEETS_PROVIDER))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::error::Result;
+    use crate::helper::docs::HelpProvider;
 
     #[test]
     fn test_fetch_cheatsheets() -> Result<()> {
-        let output = Cheatsheets.fetch("ls", &None)?;
+        let output = provider(None).fetch("ls")?.content;
         assert!(output.contains(
             r##"# To display everything in <dir>, including hidden files:
 ls -a <dir>
@@ -0,0 +1,164 @@
+use crate::error::{Error, Result};
+use crate::helper::docs::http::QUERY_PLACEHOLDER;
+use crate::helper::docs::{ContentFormat, HelpPage, HelpProvider};
+use regex::Regex;
+use std::process::{Command, Stdio};
+
+/// Provider that captures the output of a local command (e.g. `man`).
+///
+/// The command is run via the shell and built from a template that contains the
+/// [`QUERY_PLACEHOLDER`] (e.g. `man {cmd}`).
+#[derive(Clone, Debug)]
+pub struct CommandProvider {
+    /// Name of the provider.
+    name: String,
+    /// Command template.
+    command: String,
+    /// Pattern that matches the outputs (or the errors of the failed commands) which mean that
+    /// the page is not found.
+    not_found: Option<Regex>,
+    /// Format of the pages.
+    format: ContentFormat,
+}
+
+impl CommandProvider {
+    /// Creates a new provider with the given name and command template.
+    ///
+    /// If the template does not contain the [`QUERY_PLACEHOLDER`], the query is appended as an argument.
+    pub fn new(name: &str, command: &str) -> Self {
+        let command = if command.contains(QUERY_PLACEHOLDER) {
+            command.to_string()
+        } else {
+            format!("{command} {QUERY_PLACEHOLDER}")
+        };
+        Self {
+            name: name.to_string(),
+            command,
+            not_found: None,
+            format: ContentFormat::default(),
+        }
+    }
+
+    /// Sets the pattern for detecting the outputs that mean that the page is not found.
+    pub fn not_found(mut self, pattern: Regex) -> Self {
+        self.not_found = Some(pattern);
+        self
+    }
+
+    /// Sets the format of the pages.
+    pub fn format(mut self, format: ContentFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Returns the command to run for the given query.
+    ///
+    /// The words of the query are passed to the shell as positional arguments (`"$@"`) instead
+    /// of being spliced into the command, so they are not interpreted by the shell.
+    pub fn get_command(&self, query: &str) -> Command {
+        let words = query.split_whitespace();
+        if cfg!(target_os = "windows") {
+            // `cmd` has no positional arguments, so the words are quoted instead.
+            let query = words
+                .map(|word| format!("\"{}\"", word.replace(['"', '%'], "")))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let mut command = Command::new("cmd");
+            command.args(["/C", &self.command.replace(QUERY_PLACEHOLDER, &query)]);
+            command
+        } else {
+            let mut command = Command::new("sh");
+            command
+                .args([
+                    "-c",
+                    &self.command.replace(QUERY_PLACEHOLDER, "\"$@\""),
+                    "sh",
+                ])
+                .args(words)
+                .env("MANPAGER", "cat")
+                .env("PAGER", "cat")
+                .stdin(Stdio::null());
+            command
+        }
+    }
+}
+
+impl HelpProvider for CommandProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        let output = self.get_command(query).output()?;
+        if !output.status.success() || output.stdout.is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if let Some(ref not_found) = self.not_found {
+                if not_found.is_match(stderr.trim()) {
+                    return Err(Error::NotFoundError(stderr.trim().to_string()));
+                }
+            }
+            return Err(Error::ProviderError(if stderr.trim().is_empty() {
+                format!(
+                    "`{}` failed with {}",
+                    self.command.replace(QUERY_PLACEHOLDER, query),
+                    output.status
+                )
+            } else {
+                stderr.trim().to_string()
+            }));
+        }
+        let content = String::from_utf8_lossy(&output.stdout).to_string();
+        if let Some(ref not_found) = self.not_found {
+            if not_found.is_match(&content) {
+                return Err(Error::NotFoundError(content));
+            }
+        }
+        Ok(HelpPage {
+            provider: self.name.to_string(),
+            content,
+            format: self.format,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_fetch_command() -> Result<()> {
+        let provider = CommandProvider::new("echo", "echo page of");
+        assert_eq!(
+            HelpPage {
+                provider: String::from("echo"),
+                content: String::from("page of ls\n"),
+                format: ContentFormat::Plain,
+                ..Default::default()
+            },
+            provider.fetch("ls")?
+        );
+        assert_eq!(
+            "page of ls;touch pwned $(id)\n",
+            provider.fetch("ls;touch pwned $(id)")?.content
+        );
+        assert_eq!(
+            Some(ErrorKind::Other),
+            CommandProvider::new("fail", "false {cmd}")
+                .fetch("ls")
+                .err()
+                .map(|e| e.kind())
+        );
+        assert_eq!(
+            Some(ErrorKind::NotFound),
+            CommandProvider::new("missing", "echo no page for {cmd} >&2; false")
+                .not_found(Regex::new("^no page")?)
+                .fetch("ls")
+                .err()
+                .map(|e| e.kind())
+        );
+        Ok(())
+    }
+}
@@ -1,36 +1,26 @@
-use crate::helper::docs::HelpProvider;
-use ureq::Agent;
+use crate::helper::docs::http::HttpProvider;
 
 /// EG page provider URL.
 pub const DEFAULT_EG_PAGES_PROVIDER: &str =
     "https://raw.githubusercontent.com/srsudar/eg/master/eg/examples";
 
-/// The `eg` pages provider
-pub struct Eg;
-
-impl HelpProvider for Eg {
-    fn url(&self) -> &'static str {
-        DEFAULT_EG_PAGES_PROVIDER
-    }
-
-    fn build_request(
-        &self,
-        cmd: &str,
-        url: &str,
-    ) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
-        let agent: Agent = Agent::config_builder().build().into();
-        agent.get(&format!("{}/{}.md", url, cmd))
-    }
+/// Creates the `eg` pages provider with the given URL (defaults to [`DEFAULT_EG_PAGES_PROVIDER`]).
+pub fn provider(url: Option<&str>) -> HttpProvider {
+    HttpProvider::new(
+        "eg",
+        &format!("{}/{{cmd}}.md", url.unwrap_or(DEFAULT_EG_PAGES_PROVIDER)),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::error::Result;
+    use crate::helper::docs::HelpProvider;
 
     #[test]
     fn test_eg_page_fetch() -> Result<()> {
-        let output = Eg.fetch("ls", &None)?;
+        let output = provider(None).fetch("ls")?.content;
         assert!(output.contains("show contents of current directory"));
         assert!(output.contains("ls -alh"));
         assert!(output.contains(
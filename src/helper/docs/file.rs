@@ -0,0 +1,127 @@
+use crate::error::{Error, Result};
+use crate::helper::docs::{ContentFormat, HelpPage, HelpProvider};
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// Provider that reads the pages from a local directory.
+///
+/// The page of a query is looked up as `<dir>/<query>` followed by `<dir>/<query>.<extension>`
+/// for each of the given extensions.
+#[derive(Clone, Debug)]
+pub struct FileProvider {
+    /// Name of the provider.
+    name: String,
+    /// Directory of the pages.
+    dir: PathBuf,
+    /// File extensions to try (e.g. `md`).
+    extensions: Vec<String>,
+    /// Format of the pages.
+    format: ContentFormat,
+}
+
+impl FileProvider {
+    /// Creates a new provider that reads the pages from the given directory.
+    pub fn new(name: &str, dir: PathBuf) -> Self {
+        Self {
+            name: name.to_string(),
+            dir,
+            extensions: Vec::new(),
+            format: ContentFormat::default(),
+        }
+    }
+
+    /// Sets the format of the pages.
+    pub fn format(mut self, format: ContentFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Adds a file extension to try.
+    pub fn extension(mut self, extension: &str) -> Self {
+        self.extensions.push(extension.to_string());
+        self
+    }
+
+    /// Returns the path of the page for the given query if it exists.
+    pub fn find_page(&self, query: &str) -> Option<PathBuf> {
+        if !is_inner_path(query) {
+            return None;
+        }
+        let path = self.dir.join(query);
+        if path.is_file() {
+            return Some(path);
+        }
+        self.extensions
+            .iter()
+            .map(|extension| self.dir.join(format!("{query}.{extension}")))
+            .find(|path| path.is_file())
+    }
+}
+
+/// Returns `true` if the given path stays inside the directory that it is joined to.
+///
+/// Only plain names are allowed, so that e.g. `../../etc/passwd` or `/etc/passwd` cannot escape
+/// the directory.
+pub fn is_inner_path(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+impl HelpProvider for FileProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        let path = self.find_page(query).ok_or_else(|| {
+            Error::NotFoundError(format!("No page for `{query}` in {:?}", self.dir))
+        })?;
+        Ok(HelpPage {
+            provider: self.name.to_string(),
+            content: fs::read_to_string(path)?,
+            format: self.format,
+            ..Default::default()
+        })
+    }
+}
+
+/// Reads the file as text, decompressing it if it is gzipped (e.g. `ls.1.gz`).
+pub fn read_file(path: &Path) -> Result<String> {
+    let mut contents = Vec::new();
+    if path.extension().and_then(|v| v.to_str()) == Some("gz") {
+        GzDecoder::new(File::open(path)?).read_to_end(&mut contents)?;
+    } else {
+        contents = fs::read(path)?;
+    }
+    Ok(String::from_utf8_lossy(&contents).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_fetch_file() -> Result<()> {
+        let dir = TestDir::new("file")?;
+        fs::create_dir_all(dir.join("git"))?;
+        fs::write(dir.join("tar"), "# tar")?;
+        fs::write(dir.join("git").join("commit.md"), "# git commit")?;
+        let provider = FileProvider::new("local", dir.to_path_buf()).extension("md");
+        assert_eq!("# tar", provider.fetch("tar")?.content);
+        assert_eq!("# git commit", provider.fetch("git/commit")?.content);
+        assert!(provider.fetch("ls").is_err());
+        assert!(provider.fetch("../tar").is_err());
+        // Absolute paths replace the directory when joined.
+        assert!(provider
+            .fetch(&dir.join("tar").to_string_lossy())
+            .is_err_and(|e| e.kind() == ErrorKind::NotFound));
+        assert!(!is_inner_path("./tar"));
+        Ok(())
+    }
+}
@@ -0,0 +1,402 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::helper::args::get_timeout;
+use crate::helper::docs::cache::{now, CacheEntry, PageCache};
+use crate::helper::docs::file::FileProvider;
+use crate::helper::docs::{ContentFormat, HelpPage, HelpProvider};
+use regex::Regex;
+use std::io::ErrorKind as IoErrorKind;
+use std::path::PathBuf;
+use std::time::Duration;
+use ureq::http::StatusCode;
+use ureq::Agent;
+
+/// Placeholder for the query in URL and command templates.
+pub const QUERY_PLACEHOLDER: &str = "{cmd}";
+
+/// Provider that fetches the pages via HTTP `GET` requests.
+///
+/// The URL is built from a template that contains the [`QUERY_PLACEHOLDER`]
+/// (e.g. `https://cheat.sh/{cmd}`).
+///
+/// If a mirror directory is set (see `halp sync`), the pages are read from there first.
+/// If a [`PageCache`] is set, the fetched pages are stored on disk. Expired pages are revalidated
+/// with the `ETag`/`Last-Modified` headers and stale pages are used when the provider is unreachable.
+#[derive(Clone, Debug)]
+pub struct HttpProvider {
+    /// Name of the provider.
+    name: String,
+    /// URL template.
+    url: String,
+    /// User agent to send.
+    user_agent: Option<String>,
+    /// Additional headers to send.
+    headers: Vec<(String, String)>,
+    /// Pattern that matches the responses which mean that the page is not found.
+    not_found: Option<Regex>,
+    /// Format of the pages.
+    format: ContentFormat,
+    /// Cache of the pages.
+    cache: Option<PageCache>,
+    /// Local mirror of the pages.
+    mirror: Option<PathBuf>,
+    /// Whether the query is put into the URL as is.
+    raw_query: bool,
+    /// Timeout of the requests in seconds.
+    timeout: u64,
+}
+
+impl HttpProvider {
+    /// Creates a new provider with the given name and URL template.
+    ///
+    /// If the template does not contain the [`QUERY_PLACEHOLDER`], the query is appended as a path segment.
+    pub fn new(name: &str, url: &str) -> Self {
+        let url = if url.contains(QUERY_PLACEHOLDER) {
+            url.to_string()
+        } else {
+            format!("{}/{QUERY_PLACEHOLDER}", url.trim_end_matches('/'))
+        };
+        Self {
+            name: name.to_string(),
+            url,
+            user_agent: None,
+            headers: Vec::new(),
+            not_found: None,
+            format: ContentFormat::default(),
+            cache: None,
+            mirror: None,
+            raw_query: false,
+            timeout: get_timeout(&Config::default()),
+        }
+    }
+
+    /// Sets the user agent.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Adds a header to send with the requests.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the pattern for detecting the responses that mean that the page is not found.
+    pub fn not_found(mut self, pattern: Regex) -> Self {
+        self.not_found = Some(pattern);
+        self
+    }
+
+    /// Sets the format of the pages.
+    pub fn format(mut self, format: ContentFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the cache of the pages.
+    pub fn cache(mut self, cache: Option<PageCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Sets the local mirror directory of the pages.
+    pub fn mirror(mut self, mirror: Option<PathBuf>) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// Sets the timeout of the requests in seconds.
+    pub fn timeout(mut self, timeout: u64) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Puts the query into the URL as is.
+    ///
+    /// This is for the providers that encode the query themselves since it is a path
+    /// (e.g. `pages/common/tar.md` for tldr) or uses a query language (e.g. cheat.sh).
+    pub fn raw_query(mut self) -> Self {
+        self.raw_query = true;
+        self
+    }
+
+    /// Returns the path of the page relative to the mirror directory.
+    ///
+    /// This is the part of the URL after the base URL (e.g. `tar.md` for `https://example.com/{cmd}.md`).
+    pub fn get_mirror_path(&self, query: &str) -> String {
+        self.url
+            .find(QUERY_PLACEHOLDER)
+            .map(|index| &self.url[index..])
+            .unwrap_or(QUERY_PLACEHOLDER)
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .replace(QUERY_PLACEHOLDER, query)
+    }
+
+    /// Returns the URL of the page for the given query.
+    ///
+    /// The query is percent-encoded as a single path segment (e.g. `/` and `?` are encoded as
+    /// well), unless it is [raw](HttpProvider::raw_query).
+    pub fn get_url(&self, query: &str) -> String {
+        if self.raw_query {
+            self.url.replace(QUERY_PLACEHOLDER, query)
+        } else {
+            self.url
+                .replace(QUERY_PLACEHOLDER, &percent_encode(query, b""))
+        }
+    }
+
+    /// Builds an HTTP request for the given query.
+    fn build_request(&self, query: &str) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
+        let agent = build_agent(self.user_agent.as_deref(), self.timeout);
+        let mut request = agent.get(&self.get_url(query));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+
+    /// Returns the page from the cache entry.
+    ///
+    /// The age of the entry is set if it is stale.
+    fn cached_page(&self, entry: &CacheEntry, stale: bool) -> HelpPage {
+        HelpPage {
+            provider: self.name.to_string(),
+            content: entry.content.to_string(),
+            format: entry.format,
+            stale_age: stale.then(|| entry.age()),
+        }
+    }
+
+    /// Handles the request error.
+    ///
+    /// Returns a custom message if the error means that the provider doesn't have a page for the query.
+    /// Rate limiting (`429 Too Many Requests`) is reported as a server error.
+    fn handle_error(&self, e: ureq::Error) -> Error {
+        match e {
+            ureq::Error::Timeout(_) => Error::TimeoutError(self.timeout),
+            ureq::Error::Io(ref e) if e.kind() == IoErrorKind::TimedOut => {
+                Error::TimeoutError(self.timeout)
+            }
+            ureq::Error::StatusCode(code) if code == 429 || code >= 500 => Error::ServerError(code),
+            ureq::Error::StatusCode(404 | 410) => Error::NotFoundError(
+                "Unknown topic, This topic/command might has no page in this provider yet."
+                    .to_string(),
+            ),
+            e => Error::from(Box::new(e)),
+        }
+    }
+}
+
+/// Builds the HTTP agent with the given user agent and timeout (in seconds).
+///
+/// The timeout covers the whole request, including reading the response body. Connecting and
+/// waiting for the response are limited by the timeout even if a request raises the global limit.
+pub fn build_agent(user_agent: Option<&str>, timeout: u64) -> Agent {
+    let timeout = Some(Duration::from_secs(timeout));
+    let mut config = Agent::config_builder()
+        .timeout_global(timeout)
+        .timeout_connect(timeout)
+        .timeout_recv_response(timeout);
+    if let Some(user_agent) = user_agent {
+        config = config.user_agent(user_agent);
+    }
+    config.build().into()
+}
+
+/// Percent-encodes the text, except for the unreserved characters of the URLs and the given ones.
+pub fn percent_encode(text: &str, keep: &[u8]) -> String {
+    text.bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"-._~".contains(&b) || keep.contains(&b) {
+                char::from(b).to_string()
+            } else {
+                format!("%{b:02X}")
+            }
+        })
+        .collect()
+}
+
+impl HelpProvider for HttpProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        if let Some(ref mirror) = self.mirror {
+            let mirror = FileProvider::new(&self.name, mirror.to_path_buf()).format(self.format);
+            let path = self.get_mirror_path(query);
+            if mirror.find_page(&path).is_some() {
+                return mirror.fetch(&path);
+            }
+        }
+        // The pages are cached by their URL since the same query might resolve to different pages.
+        let key = self.get_url(query);
+        let cached = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(&self.name, &key).map(|entry| (cache, entry)));
+        if let Some((cache, ref entry)) = cached {
+            if cache.is_fresh(entry) {
+                return Ok(self.cached_page(entry, false));
+            }
+        }
+        let mut request = self.build_request(query);
+        if let Some((_, ref entry)) = cached {
+            if let Some(ref etag) = entry.etag {
+                request = request.header("If-None-Match", etag);
+            }
+            if let Some(ref last_modified) = entry.last_modified {
+                request = request.header("If-Modified-Since", last_modified);
+            }
+        }
+        let mut response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::StatusCode(code)) if code < 500 && code != 429 => {
+                return Err(self.handle_error(ureq::Error::StatusCode(code)))
+            }
+            // Use the stale page if the provider is unreachable or fails.
+            Err(e) => {
+                return match cached {
+                    Some((_, ref entry)) => Ok(self.cached_page(entry, true)),
+                    None => Err(self.handle_error(e)),
+                }
+            }
+        };
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some((cache, mut entry)) = cached {
+                entry.fetched_at = now().as_secs();
+                cache.set(&self.name, &key, &entry)?;
+                return Ok(self.cached_page(&entry, false));
+            }
+        }
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        let (etag, last_modified) = (header("etag"), header("last-modified"));
+        let content = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|e| self.handle_error(e))?;
+        if let Some(ref not_found) = self.not_found {
+            if not_found.is_match(&content) {
+                return Err(Error::NotFoundError(content));
+            }
+        }
+        if let Some(ref cache) = self.cache {
+            cache.set(
+                &self.name,
+                &key,
+                &CacheEntry {
+                    content: content.to_string(),
+                    format: self.format,
+                    etag,
+                    last_modified,
+                    fetched_at: now().as_secs(),
+                },
+            )?;
+        }
+        Ok(HelpPage {
+            provider: self.name.to_string(),
+            content,
+            format: self.format,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_get_url() {
+        assert_eq!(
+            "https://example.com/ls",
+            HttpProvider::new("test", "https://example.com/").get_url("ls")
+        );
+        assert_eq!(
+            "https://example.com/pages/ls.md",
+            HttpProvider::new("test", "https://example.com/pages/{cmd}.md").get_url("ls")
+        );
+        assert_eq!(
+            "pages/common/ls.md",
+            HttpProvider::new("test", "https://example.com/{cmd}.md?raw=true")
+                .get_mirror_path("pages/common/ls")
+        );
+        assert_eq!(
+            "https://example.com/git%20commit%2F%C3%BC%25%3FT%26a%3Db",
+            HttpProvider::new("test", "https://example.com").get_url("git commit/ü%?T&a=b")
+        );
+        assert_eq!(
+            "https://example.com/pages/common/tar.md",
+            HttpProvider::new("test", "https://example.com")
+                .raw_query()
+                .get_url("pages/common/tar.md")
+        );
+    }
+
+    #[test]
+    fn test_fetch_cached() -> Result<()> {
+        let dir = TestDir::new("http-cache")?;
+        let cache = PageCache::new(dir.to_path_buf(), Duration::from_secs(60));
+        // Nothing listens on this port so the requests fail.
+        let provider = HttpProvider::new("test", "http://127.0.0.1:1").cache(Some(cache.clone()));
+        assert!(provider.fetch("ls").is_err());
+        let mut entry = CacheEntry {
+            content: String::from("ls page"),
+            format: ContentFormat::Plain,
+            etag: None,
+            last_modified: None,
+            fetched_at: now().as_secs(),
+        };
+        cache.set("test", &provider.get_url("ls"), &entry)?;
+        assert_eq!(
+            HelpPage {
+                provider: String::from("test"),
+                content: String::from("ls page"),
+                format: ContentFormat::Plain,
+                stale_age: None,
+            },
+            provider.fetch("ls")?
+        );
+        fs::create_dir_all(dir.join("mirror"))?;
+        fs::write(dir.join("mirror").join("tar"), "tar page")?;
+        let mirrored = provider.clone().mirror(Some(dir.join("mirror")));
+        assert_eq!("tar page", mirrored.fetch("tar")?.content);
+        assert_eq!("ls page", mirrored.fetch("ls")?.content);
+        entry.fetched_at -= 3600;
+        cache.set("test", &provider.get_url("ls"), &entry)?;
+        assert_eq!(
+            Some(Duration::from_secs(3600)),
+            provider
+                .fetch("ls")?
+                .stale_age
+                .map(|age| Duration::from_secs(age.as_secs() / 60 * 60))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_timeout() -> Result<()> {
+        // The connections are accepted by the kernel but never replied to.
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let provider =
+            HttpProvider::new("test", &format!("http://{}", listener.local_addr()?)).timeout(1);
+        let error = provider.fetch("ls").err();
+        assert_eq!(Some(ErrorKind::Timeout), error.as_ref().map(Error::kind));
+        assert!(matches!(error, Some(Error::TimeoutError(1))));
+        Ok(())
+    }
+}
@@ -0,0 +1,407 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::helper::docs::file::read_file;
+use crate::helper::docs::{get_selection_theme, show_page, HelpPage, HelpProvider};
+use console::Term;
+use dialoguer::Select;
+use regex::Regex;
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Default directories of the info files.
+const DEFAULT_INFO_DIRS: &[&str] = &["/usr/local/share/info", "/usr/share/info", "/usr/info"];
+
+/// Separator of the nodes in the info files.
+const NODE_SEPARATOR: char = '\x1f';
+
+/// Name of the top node of an info file.
+const TOP_NODE: &str = "Top";
+
+/// Reference to a node (e.g. `(coreutils)ls invocation`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeRef {
+    /// Name of the info file (e.g. `coreutils`).
+    pub file: String,
+    /// Name of the node (e.g. `ls invocation`).
+    pub node: String,
+}
+
+impl NodeRef {
+    /// Parses the reference, using the given file if the reference does not contain one.
+    pub fn parse(reference: &str, file: &str) -> Self {
+        let reference = reference.trim();
+        match reference
+            .strip_prefix('(')
+            .and_then(|reference| reference.split_once(')'))
+        {
+            Some((file, node)) => Self {
+                file: file.trim().to_string(),
+                node: Some(node.trim())
+                    .filter(|node| !node.is_empty())
+                    .unwrap_or(TOP_NODE)
+                    .to_string(),
+            },
+            None => Self {
+                file: file.to_string(),
+                node: reference.to_string(),
+            },
+        }
+    }
+}
+
+/// A node of an info file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InfoNode {
+    /// Reference to the node.
+    pub reference: NodeRef,
+    /// Next node.
+    pub next: Option<NodeRef>,
+    /// Previous node.
+    pub prev: Option<NodeRef>,
+    /// Parent node.
+    pub up: Option<NodeRef>,
+    /// Menu entries of the node (label and reference).
+    pub menu: Vec<(String, NodeRef)>,
+    /// Text of the node, including its header line.
+    pub content: String,
+}
+
+/// Provider for the GNU info pages.
+///
+/// The info files (`<name>.info` or `<name>.info.gz`, possibly split into `<name>.info-<n>`) are
+/// looked up in the `INFOPATH` directories followed by the default directories. A command is
+/// resolved to its node with the `dir` files (e.g. `ls` to `(coreutils)ls invocation`), falling
+/// back to the top node of the info file with the same name.
+#[derive(Clone, Debug)]
+pub struct InfoProvider {
+    /// Directories of the info files.
+    dirs: Vec<PathBuf>,
+}
+
+impl InfoProvider {
+    /// Creates a new provider for the given directories.
+    pub fn new(dirs: Vec<PathBuf>) -> Self {
+        Self { dirs }
+    }
+
+    /// Creates a provider for the `INFOPATH` and the default directories.
+    pub fn from_env() -> Self {
+        let mut dirs = env::var_os("INFOPATH")
+            .map(|path| env::split_paths(&path).collect::<Vec<_>>())
+            .unwrap_or_default();
+        dirs.extend(DEFAULT_INFO_DIRS.iter().map(PathBuf::from));
+        dirs.retain(|dir| !dir.as_os_str().is_empty());
+        Self::new(dirs)
+    }
+
+    /// Returns the path of the given file in the given directory, trying the known extensions.
+    fn find_in_dir(dir: &Path, name: &str) -> Option<PathBuf> {
+        [
+            format!("{name}.info"),
+            format!("{name}.info.gz"),
+            name.to_string(),
+            format!("{name}.gz"),
+        ]
+        .iter()
+        .map(|file| dir.join(file))
+        .find(|path| path.is_file())
+    }
+
+    /// Returns the path of the info file with the given name.
+    fn find_file(&self, name: &str) -> Option<PathBuf> {
+        if name.contains("..") || name.contains('/') {
+            return None;
+        }
+        self.dirs
+            .iter()
+            .find_map(|dir| Self::find_in_dir(dir, name))
+    }
+
+    /// Reads the info file with the subfiles of a split file.
+    fn read_document(&self, name: &str) -> Result<String> {
+        let path = self
+            .find_file(name)
+            .ok_or_else(|| Error::NotFoundError(format!("No info file for `{name}`")))?;
+        let contents = read_file(&path)?;
+        let Some(indirect) = contents
+            .split(NODE_SEPARATOR)
+            .find_map(|section| section.trim_start().strip_prefix("Indirect:"))
+        else {
+            return Ok(contents);
+        };
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let mut document = String::new();
+        for subfile in indirect
+            .lines()
+            .filter_map(|line| line.rsplit_once(':').map(|(name, _)| name.trim()))
+            .filter(|name| !name.is_empty() && !name.contains('/'))
+        {
+            let path = Self::find_in_dir(dir, subfile)
+                .ok_or_else(|| Error::ProviderError(format!("Missing info subfile `{subfile}`")))?;
+            document.push_str(&read_file(&path)?);
+        }
+        Ok(document)
+    }
+
+    /// Returns `true` if any of the info directories exists.
+    pub fn has_dirs(&self) -> bool {
+        self.dirs.iter().any(|dir| dir.is_dir())
+    }
+
+    /// Returns the node of the command from the directory entries.
+    ///
+    /// The `dir` files are searched first, followed by the `START-INFO-DIR-ENTRY` section of the
+    /// info file with the same name as the command (e.g. when `install-info` is not run).
+    fn find_dir_entry(&self, cmd: &str) -> Option<NodeRef> {
+        let entry = Regex::new(r"(?m)^\* ([^:]+):\s*(\([^)]+\)[^.\t\n]*)\.").ok()?;
+        let dir_files = self
+            .dirs
+            .iter()
+            .filter_map(|dir| Self::find_in_dir(dir, "dir"))
+            .filter_map(|path| read_file(&path).ok());
+        let info_files = self.find_file(cmd).into_iter().filter_map(|path| {
+            read_file(&path).ok().map(|contents| {
+                contents
+                    .split(NODE_SEPARATOR)
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+        });
+        dir_files.chain(info_files).find_map(|contents| {
+            entry
+                .captures_iter(&contents)
+                .find(|captures| captures[1].trim().eq_ignore_ascii_case(cmd))
+                .map(|captures| NodeRef::parse(&captures[2], ""))
+        })
+    }
+
+    /// Resolves the query to a node.
+    ///
+    /// The query is either a command (e.g. `ls`) or a node reference (e.g. `(coreutils)Top`).
+    pub fn resolve(&self, query: &str) -> Result<NodeRef> {
+        let query = query.trim();
+        if query.starts_with('(') {
+            return Ok(NodeRef::parse(query, ""));
+        }
+        if let Some(reference) = self.find_dir_entry(query) {
+            return Ok(reference);
+        }
+        if self.find_file(query).is_some() {
+            return Ok(NodeRef {
+                file: query.to_string(),
+                node: TOP_NODE.to_string(),
+            });
+        }
+        Err(Error::NotFoundError(format!("No info page for `{query}`")))
+    }
+
+    /// Reads the given node.
+    pub fn read_node(&self, reference: &NodeRef) -> Result<InfoNode> {
+        let document = self.read_document(&reference.file)?;
+        let field = Regex::new(r"(File|Node|Next|Prev|Up):\s*([^,\t]+)")?;
+        document
+            .split(NODE_SEPARATOR)
+            .filter_map(|section| parse_node(section, &reference.file, &field))
+            .find(|node| node.reference.node.eq_ignore_ascii_case(&reference.node))
+            .ok_or_else(|| {
+                Error::ProviderError(format!(
+                    "No node `{}` in the info file `{}`",
+                    reference.node, reference.file
+                ))
+            })
+    }
+}
+
+/// Parses the node from a section of the info file.
+///
+/// Returns `None` if the section is not a node (e.g. the tag table).
+fn parse_node(section: &str, file: &str, field: &Regex) -> Option<InfoNode> {
+    let section = section.trim_start_matches(['\r', '\n']);
+    let header = section.lines().next()?;
+    if !header.starts_with("File:") {
+        return None;
+    }
+    let mut node = InfoNode {
+        content: section.trim_end().to_string(),
+        ..Default::default()
+    };
+    for captures in field.captures_iter(header) {
+        let reference = NodeRef::parse(&captures[2], file);
+        match &captures[1] {
+            "Node" => node.reference = reference,
+            "Next" => node.next = Some(reference),
+            "Prev" => node.prev = Some(reference),
+            "Up" => node.up = Some(reference),
+            _ => {}
+        }
+    }
+    if node.reference.node.is_empty() {
+        return None;
+    }
+    let menu = section
+        .split_once("\n* Menu:")
+        .map(|(_, menu)| menu)
+        .unwrap_or_default();
+    for entry in menu.lines().filter_map(|line| line.strip_prefix("* ")) {
+        let (label, target) = match entry.split_once("::") {
+            Some((label, _)) => (label, label),
+            None => match entry.split_once(": ") {
+                Some((label, target)) => (
+                    label,
+                    target
+                        .trim_start()
+                        .split(['.', ',', '\t'])
+                        .next()
+                        .unwrap_or_default(),
+                ),
+                None => continue,
+            },
+        };
+        node.menu
+            .push((label.trim().to_string(), NodeRef::parse(target, file)));
+    }
+    Some(node)
+}
+
+impl HelpProvider for InfoProvider {
+    fn name(&self) -> &str {
+        "info"
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        let node = self.read_node(&self.resolve(query)?)?;
+        Ok(HelpPage {
+            provider: self.name().to_string(),
+            content: node.content,
+            ..Default::default()
+        })
+    }
+}
+
+/// Shows the info page of the given query and lets the user navigate between the nodes.
+pub fn navigate_info<Output: Write>(
+    query: &str,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let provider = InfoProvider::from_env();
+    let mut reference = provider.resolve(query)?;
+    loop {
+        let node = provider.read_node(&reference)?;
+        show_page(
+            &HelpPage {
+                provider: provider.name().to_string(),
+                content: node.content.to_string(),
+                ..Default::default()
+            },
+            config,
+            output,
+        )?;
+        let mut links = Vec::new();
+        for (label, link) in [("Next", &node.next), ("Prev", &node.prev), ("Up", &node.up)] {
+            if let Some(link) = link {
+                links.push((format!("{label}: {}", link.node), link.clone()));
+            }
+        }
+        for (label, link) in &node.menu {
+            links.push((format!("Menu: {label}"), link.clone()));
+        }
+        let mut menu_options = links
+            .iter()
+            .map(|(label, _)| label.to_string())
+            .collect::<Vec<_>>();
+        menu_options.push(String::from("Exit"));
+        let selection = Select::with_theme(&get_selection_theme())
+            .with_prompt(format!("({}){}", node.reference.file, node.reference.node))
+            .default(0)
+            .items(&menu_options)
+            .interact_on_opt(&Term::stderr())?;
+        match selection.and_then(|index| links.get(index)) {
+            Some((_, link)) => reference = link.clone(),
+            None => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::test_dir::TestDir;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    #[test]
+    fn test_info_provider() -> Result<()> {
+        let dir = TestDir::new("info")?;
+        fs::write(
+            dir.join("dir"),
+            "\x1f\nFile: dir\tNode: Top\tThis is the top of the INFO tree\n\n* Menu:\n\n\
+             * Tar: (tar).                   Making tape archives.\n\
+             * ls: (coreutils)ls invocation.  List directory contents.\n",
+        )?;
+        fs::write(
+            dir.join("coreutils.info"),
+            "This is coreutils.info.\n\x1f\nIndirect:\ncoreutils.info-1: 100\n\x1f\nTag Table:\n",
+        )?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(
+            "\x1f\nFile: coreutils.info,  Node: Top,  Next: ls invocation,  Up: (dir)\n\n\
+             GNU Coreutils\n\n* Menu:\n\n* ls invocation::  List directory contents.\n\
+             * Listing: ls invocation.  Same node.\n\
+             \x1f\nFile: coreutils.info,  Node: ls invocation,  Prev: Top,  Up: Top\n\n\
+             10.1 'ls': List directory contents\n"
+                .as_bytes(),
+        )?;
+        fs::write(dir.join("coreutils.info-1.gz"), encoder.finish()?)?;
+
+        let provider = InfoProvider::new(vec![dir.to_path_buf()]);
+        let reference = provider.resolve("ls")?;
+        assert_eq!(
+            NodeRef {
+                file: String::from("coreutils"),
+                node: String::from("ls invocation"),
+            },
+            reference
+        );
+        assert_eq!(
+            "File: coreutils.info,  Node: ls invocation,  Prev: Top,  Up: Top\n\n\
+             10.1 'ls': List directory contents",
+            provider.fetch("ls")?.content
+        );
+        let top = provider.read_node(&provider.resolve("coreutils")?)?;
+        assert_eq!(Some(NodeRef::parse("(dir)", "coreutils")), top.up);
+        assert_eq!(
+            vec![
+                (
+                    String::from("ls invocation"),
+                    NodeRef::parse("ls invocation", "coreutils")
+                ),
+                (
+                    String::from("Listing"),
+                    NodeRef::parse("ls invocation", "coreutils")
+                ),
+            ],
+            top.menu
+        );
+        assert!(provider.fetch("tar").is_err());
+        assert!(provider.fetch("(coreutils)missing").is_err());
+
+        fs::write(
+            dir.join("sed.info"),
+            "START-INFO-DIR-ENTRY\n* sed: (sed)Invoking sed.  Stream editor.\n\
+             * grep: (grep)Usage.  Print matching lines.\nEND-INFO-DIR-ENTRY\n",
+        )?;
+        assert_eq!(
+            NodeRef::parse("(sed)Invoking sed", ""),
+            provider.resolve("sed")?
+        );
+        assert!(provider.resolve("grep").is_err());
+        assert!(provider.has_dirs());
+        assert!(!InfoProvider::new(vec![dir.join("missing")]).has_dirs());
+        Ok(())
+    }
+}
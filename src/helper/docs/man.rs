@@ -1,14 +1,738 @@
-use crate::error::Result;
-use std::process::Command;
+use crate::error::{Error, Result};
+use crate::helper::args::parser::sanitize;
+use crate::helper::args::FOUND_EMOTICON;
+use crate::helper::docs::command::CommandProvider;
+use crate::helper::docs::file::read_file;
+use crate::helper::docs::roff::{render_roff, DEFAULT_WIDTH};
+use crate::helper::docs::{HelpPage, HelpProvider};
+use colored::*;
+use console::Term;
+use regex::{Regex, RegexBuilder};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
 
-/// Runs the manual page command.
-pub fn show_man_page(man_cmd: &str, cmd: &str) -> Result<()> {
+/// Name of the provider.
+pub const MAN_PROVIDER: &str = "man";
+
+/// Name of the provider for the examples in the manual pages.
+pub const MAN_EXAMPLES_PROVIDER: &str = "man-examples";
+
+/// Names of the sections that contain the examples.
+const EXAMPLES_SECTIONS: &[&str] = &["EXAMPLES", "EXAMPLE"];
+
+/// Order of the manual sections when a page exists in multiple sections.
+pub const MAN_SECTIONS: &[&str] = &["1", "n", "l", "8", "3", "0", "2", "5", "4", "9", "6", "7"];
+
+/// Default directories of the manual pages.
+const DEFAULT_MAN_DIRS: &[&str] = &[
+    "/usr/local/share/man",
+    "/usr/share/man",
+    "/usr/local/man",
+    "/usr/man",
+];
+
+/// Name of the `whatis` database in the text format (e.g. on BSD and macOS).
+const WHATIS_FILE: &str = "whatis";
+
+/// Maximum number of `.so` redirections to follow.
+const MAX_SO_DEPTH: usize = 5;
+
+/// A manual page on the disk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManPage {
+    /// Name of the page (e.g. `ls`).
+    pub name: String,
+    /// Section of the page (e.g. `1`).
+    pub section: String,
+    /// Path of the page (e.g. `/usr/share/man/man1/ls.1.gz`).
+    pub path: PathBuf,
+}
+
+/// Returns the directories that are searched for the manual pages.
+///
+/// `$MANPATH` is used if it is set, where an empty component means the default directories.
+/// Otherwise, the directories are derived from `$PATH` (e.g. `/usr/bin` to `/usr/share/man`)
+/// followed by the default directories.
+pub fn get_man_path() -> Vec<PathBuf> {
+    let defaults = || {
+        let mut dirs = Vec::new();
+        for dir in env::var_os("PATH")
+            .map(|path| env::split_paths(&path).collect::<Vec<_>>())
+            .unwrap_or_default()
+        {
+            if let Some(parent) = dir.parent() {
+                dirs.push(parent.join("share").join("man"));
+                dirs.push(parent.join("man"));
+            }
+        }
+        dirs.extend(DEFAULT_MAN_DIRS.iter().map(PathBuf::from));
+        dirs
+    };
+    let mut dirs = match env::var_os("MANPATH").filter(|path| !path.is_empty()) {
+        Some(path) => env::split_paths(&path)
+            .flat_map(|dir| {
+                if dir.as_os_str().is_empty() {
+                    defaults()
+                } else {
+                    vec![dir]
+                }
+            })
+            .collect(),
+        None => defaults(),
+    };
+    let mut seen = Vec::new();
+    dirs.retain(|dir| {
+        if seen.contains(dir) || !dir.is_dir() {
+            return false;
+        }
+        seen.push(dir.to_path_buf());
+        true
+    });
+    dirs
+}
+
+/// Returns the localized variants of the directory for `$LANG` (e.g. `<dir>/de_DE`, `<dir>/de`)
+/// followed by the directory itself.
+fn get_localized_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(lang) = env::var("LC_ALL")
+        .or_else(|_| env::var("LC_MESSAGES"))
+        .or_else(|_| env::var("LANG"))
+        .ok()
+        .filter(|lang| !lang.is_empty() && lang != "C" && lang != "POSIX")
+    {
+        let without_encoding = lang.split('.').next().unwrap_or_default();
+        let language = without_encoding.split('_').next().unwrap_or_default();
+        for name in [lang.as_str(), without_encoding, language] {
+            let path = dir.join(name);
+            if !name.is_empty() && path.is_dir() && !dirs.contains(&path) {
+                dirs.push(path);
+            }
+        }
+    }
+    dirs.push(dir.to_path_buf());
+    dirs
+}
+
+/// Returns the position of the section in the search order.
+fn section_order(section: &str) -> usize {
+    MAN_SECTIONS
+        .iter()
+        .position(|s| section.starts_with(s))
+        .unwrap_or(MAN_SECTIONS.len())
+}
+
+/// Finds the manual pages with the given name in the directories.
+///
+/// The pages are sorted by their section in the order of [`MAN_SECTIONS`]. If a section is given
+/// (e.g. `3`), only the pages in that section (including e.g. `3p`) are returned.
+pub fn find_man_pages_in(dirs: &[PathBuf], name: &str, section: Option<&str>) -> Vec<ManPage> {
+    let mut pages: Vec<ManPage> = Vec::new();
+    if name.is_empty() || name.contains('/') || name.contains("..") {
+        return pages;
+    }
+    for root in dirs.iter().flat_map(|dir| get_localized_dirs(dir)) {
+        let Ok(entries) = fs::read_dir(&root) else {
+            continue;
+        };
+        let mut section_dirs = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_dir()
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with("man"))
+            })
+            .collect::<Vec<_>>();
+        section_dirs.sort();
+        for section_dir in section_dirs {
+            let Ok(entries) = fs::read_dir(&section_dir) else {
+                continue;
+            };
+            for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+                let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                // Only uncompressed and gzip-compressed pages can be read.
+                let file_name = file_name.strip_suffix(".gz").unwrap_or(file_name);
+                let Some((page_name, page_section)) = file_name.rsplit_once('.') else {
+                    continue;
+                };
+                if page_name != name
+                    || page_section.is_empty()
+                    || section.is_some_and(|section| !page_section.starts_with(section))
+                    || pages.iter().any(|page| page.path == path)
+                {
+                    continue;
+                }
+                pages.push(ManPage {
+                    name: page_name.to_string(),
+                    section: page_section.to_string(),
+                    path,
+                });
+            }
+        }
+    }
+    pages.sort_by_key(|page| section_order(&page.section));
+    pages
+}
+
+/// Finds the manual pages with the given name on the manual path.
+///
+/// See [`get_man_path`] and [`find_man_pages_in`].
+pub fn find_man_pages(name: &str, section: Option<&str>) -> Vec<ManPage> {
+    find_man_pages_in(&get_man_path(), name, section)
+}
+
+/// Provider for the examples in the manual pages.
+///
+/// Only the EXAMPLES section (and optionally the SYNOPSIS section) of the manual page is shown,
+/// so there is no need to scroll through the whole page.
+#[derive(Clone)]
+pub struct ManExamplesProvider {
+    /// Provider of the manual pages, shared with the registry so the page is rendered once.
+    man: Arc<dyn HelpProvider>,
+    /// Whether to include the SYNOPSIS section.
+    synopsis: bool,
+}
+
+impl ManExamplesProvider {
+    /// Creates a new provider that extracts the examples from the pages of the given provider.
+    pub fn new(man: Arc<dyn HelpProvider>, synopsis: bool) -> Self {
+        Self { man, synopsis }
+    }
+}
+
+impl HelpProvider for ManExamplesProvider {
+    fn name(&self) -> &str {
+        MAN_EXAMPLES_PROVIDER
+    }
+
+    fn title(&self) -> String {
+        String::from("Show the examples from the man page")
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        let page = self.man.fetch(query)?;
+        let examples = EXAMPLES_SECTIONS
+            .iter()
+            .find_map(|name| extract_section(&page.content, name))
+            .ok_or_else(|| {
+                Error::NotFoundError(format!("No examples in the man page of `{query}`"))
+            })?;
+        let mut sections = Vec::new();
+        if self.synopsis {
+            if let Some(synopsis) = extract_section(&page.content, "SYNOPSIS") {
+                sections.push(format!("{}\n{synopsis}", "SYNOPSIS".bold()));
+            }
+        }
+        sections.push(format!("{}\n{examples}", "EXAMPLES".bold()));
+        Ok(HelpPage {
+            provider: MAN_EXAMPLES_PROVIDER.to_string(),
+            content: sections.join("\n\n"),
+            ..Default::default()
+        })
+    }
+
+    fn variants(&self, query: &str) -> Result<Vec<String>> {
+        self.man.variants(query)
+    }
+}
+
+/// Parses the query into the name and the section of the manual page.
+///
+/// The section can be given as `printf.3` or `printf(3)`. Words in the name are joined with
+/// dashes (e.g. `git commit` to `git-commit`).
+pub fn parse_man_query(query: &str) -> (String, Option<String>) {
+    let name = query.split_whitespace().collect::<Vec<_>>().join("-");
+    let is_section = |section: &str| {
+        section
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit() || c == 'n' || c == 'l')
+            && section.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+    let parsed = match name
+        .strip_suffix(')')
+        .and_then(|name| name.rsplit_once('('))
+    {
+        Some(parsed) => Some(parsed),
+        None => name.rsplit_once('.'),
+    };
+    match parsed {
+        Some((name, section)) if !name.is_empty() && is_section(section) => {
+            (name.to_string(), Some(section.to_string()))
+        }
+        _ => (name, None),
+    }
+}
+
+/// Finds the manual pages for the query (see [`parse_man_query`]).
+///
+/// If there is no page in the parsed section, the whole query is used as the name
+/// (e.g. `python3.11`).
+fn find_query_pages(query: &str) -> Vec<ManPage> {
+    match parse_man_query(query) {
+        (name, Some(section)) => {
+            let pages = find_man_pages(&name, Some(&section));
+            if pages.is_empty() {
+                find_man_pages(
+                    &query.split_whitespace().collect::<Vec<_>>().join("-"),
+                    None,
+                )
+            } else {
+                pages
+            }
+        }
+        (name, None) => find_man_pages(&name, None),
+    }
+}
+
+/// Returns the target of the redirection if the page is a `.so` link (e.g. `man1/ls.1`).
+fn get_redirection(source: &str) -> Option<&str> {
+    source
+        .lines()
+        .find(|line| !line.trim().is_empty() && !line.starts_with(".\\\""))
+        .and_then(|line| line.strip_prefix(".so "))
+}
+
+/// Reads the roff source of the manual page, following the `.so` redirections
+/// (e.g. `.so man1/ls.1`).
+pub fn read_man_page(page: &ManPage) -> Result<String> {
+    let mut path = page.path.to_path_buf();
+    for _ in 0..MAX_SO_DEPTH {
+        let source = read_file(&path)?;
+        let Some(target) = get_redirection(&source) else {
+            return Ok(source);
+        };
+        // Redirections are relative to the root of the manual directory.
+        let root = path
+            .parent()
+            .and_then(|dir| dir.parent())
+            .unwrap_or(Path::new("/"));
+        let target = root.join(target.trim());
+        path = if target.is_file() {
+            target
+        } else {
+            PathBuf::from(format!("{}.gz", target.display()))
+        };
+    }
+    Err(Error::ProviderError(format!(
+        "Too many redirections in {:?}",
+        page.path
+    )))
+}
+
+/// Returns the width of the terminal for rendering the pages.
+fn get_terminal_width() -> usize {
+    Term::stdout()
+        .size_checked()
+        .map(|(_, width)| usize::from(width))
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Provider for the manual pages.
+///
+/// The pages are found on the manual path, decompressed and rendered in-process, so the `man`
+/// command is not needed. If the page cannot be found, or a custom `man` command is configured,
+/// the command is used instead.
+#[derive(Clone, Debug)]
+pub struct ManProvider {
+    /// Provider that runs the `man` command.
+    command: CommandProvider,
+    /// Whether to render the pages in-process.
+    in_process: bool,
+}
+
+impl ManProvider {
+    /// Creates a new provider with the given `man` command.
+    pub fn new(man_cmd: &str) -> Self {
+        let command = CommandProvider::new(MAN_PROVIDER, man_cmd);
+        Self {
+            command: match Regex::new(r"(?i)\Ano manual entry") {
+                Ok(not_found) => command.not_found(not_found),
+                Err(_) => command,
+            },
+            in_process: man_cmd.trim() == "man",
+        }
+    }
+
+    /// Renders the first manual page for the query if it exists.
+    fn render(&self, query: &str, styled: bool, width: usize) -> Result<Option<String>> {
+        if !self.in_process {
+            return Ok(None);
+        }
+        match find_query_pages(query).first() {
+            Some(page) => Ok(Some(render_roff(&read_man_page(page)?, styled, width))),
+            None => Ok(None),
+        }
+    }
+}
+
+impl HelpProvider for ManProvider {
+    fn name(&self) -> &str {
+        MAN_PROVIDER
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        // The page is not styled if the output is not a terminal (e.g. a pipe).
+        let styled = control::SHOULD_COLORIZE.should_colorize();
+        match self.render(query, styled, get_terminal_width()) {
+            Ok(Some(content)) => Ok(HelpPage {
+                provider: MAN_PROVIDER.to_string(),
+                content,
+                ..Default::default()
+            }),
+            _ => match parse_man_query(query) {
+                (name, Some(section)) => self.command.fetch(&format!("{section} {name}")),
+                (_, None) => self.command.fetch(query),
+            },
+        }
+    }
+
+    fn variants(&self, query: &str) -> Result<Vec<String>> {
+        let (name, section) = parse_man_query(query);
+        if !self.in_process || section.is_some() {
+            return Ok(Vec::new());
+        }
+        let mut sections: Vec<String> = Vec::new();
+        for page in find_man_pages(&name, None) {
+            if !sections.contains(&page.section) {
+                sections.push(page.section);
+            }
+        }
+        if sections.len() < 2 {
+            return Ok(Vec::new());
+        }
+        Ok(sections
+            .iter()
+            .map(|section| format!("{name}({section})"))
+            .collect())
+    }
+}
+
+/// Returns the manual page as plain text.
+///
+/// The page is rendered in-process if possible, otherwise the manual page command is run.
+/// Returns `None` if there is no manual page for the command.
+pub fn get_man_page(man_cmd: &str, cmd: &str) -> Result<Option<String>> {
+    if let Ok(Some(page)) = ManProvider::new(man_cmd).render(cmd, false, DEFAULT_WIDTH) {
+        return Ok(Some(page));
+    }
     let command = format!("{} {}", man_cmd, cmd);
-    let mut process = if cfg!(target_os = "windows") {
-        Command::new("cmd").args(["/C", &command]).spawn()
+    let output = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(["/C", &command]).output()
     } else {
-        Command::new("sh").args(["-c", &command]).spawn()
+        Command::new("sh")
+            .args(["-c", &command])
+            .env("MANPAGER", "cat")
+            .env("PAGER", "cat")
+            .env("MAN_KEEP_FORMATTING", "0")
+            .stdin(Stdio::null())
+            .output()
     }?;
-    process.wait()?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(sanitize(&String::from_utf8_lossy(&output.stdout))))
+}
+
+/// An entry in the NAME section of a manual page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManEntry {
+    /// Names of the page (e.g. `bzip2, bunzip2`).
+    pub names: String,
+    /// Section of the page (e.g. `1`).
+    pub section: String,
+    /// Short description of the page.
+    pub description: String,
+}
+
+impl ManEntry {
+    /// Parses the entry from the NAME line (e.g. `ls - list directory contents`).
+    fn parse(line: &str, section: &str) -> Option<Self> {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        let (names, description) = [" - ", " \u{2014} ", " \u{2013} "]
+            .iter()
+            .find_map(|separator| line.split_once(separator))?;
+        Some(Self {
+            names: names.trim().to_string(),
+            section: section.to_string(),
+            description: description.trim().to_string(),
+        })
+    }
+
+    /// Parses the entry from a line of the `whatis` database (e.g. `ls(1) - list directory contents`).
+    fn parse_whatis(line: &str) -> Option<Self> {
+        let (names, description) = line.split_once(" - ")?;
+        let (names, section) = names.trim_end().strip_suffix(')')?.rsplit_once('(')?;
+        Self::parse(&format!("{names} - {description}"), section)
+    }
+}
+
+/// Extracts the entry from the NAME section of the roff source.
+fn parse_name_section(source: &str, section: &str) -> Option<ManEntry> {
+    let is_heading = |line: &str| {
+        [".SH", ".Sh", ".SS", ".Ss"]
+            .iter()
+            .any(|macro_name| line.starts_with(&format!("{macro_name} ")))
+    };
+    let mut lines = source.lines().skip_while(|line| {
+        !(is_heading(line)
+            && line[4..]
+                .trim()
+                .trim_matches('"')
+                .eq_ignore_ascii_case("NAME"))
+    });
+    let heading = lines.next()?;
+    let body = lines
+        .take_while(|line| !is_heading(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let rendered = render_roff(&format!("{heading}\n{body}"), false, DEFAULT_WIDTH);
+    ManEntry::parse(&extract_section(&rendered, "NAME")?, section)
+}
+
+/// Searches the NAME sections of the manual pages in the directories for the pattern.
+///
+/// The `whatis` database is used if it exists in the text format, otherwise the pages are
+/// scanned directly. The redirections (`.so` and symbolic links) are skipped since they are
+/// listed in the NAME section of their target.
+pub fn search_man_pages_in(dirs: &[PathBuf], pattern: &Regex) -> Result<Vec<ManEntry>> {
+    let mut entries = Vec::new();
+    for root in dirs {
+        let whatis = root.join(WHATIS_FILE);
+        if whatis.is_file() {
+            entries.extend(
+                fs::read_to_string(whatis)?
+                    .lines()
+                    .filter_map(ManEntry::parse_whatis),
+            );
+            continue;
+        }
+        let Ok(section_dirs) = fs::read_dir(root) else {
+            continue;
+        };
+        for section_dir in section_dirs.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            if !section_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("man"))
+            {
+                continue;
+            }
+            let Ok(pages) = fs::read_dir(&section_dir) else {
+                continue;
+            };
+            for path in pages.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+                if path.is_symlink() {
+                    continue;
+                }
+                let Some(section) = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| name.strip_suffix(".gz").unwrap_or(name))
+                    .and_then(|name| name.rsplit_once('.'))
+                    .map(|(_, section)| section.to_string())
+                else {
+                    continue;
+                };
+                let Ok(source) = read_file(&path) else {
+                    continue;
+                };
+                if get_redirection(&source).is_some() {
+                    continue;
+                }
+                entries.extend(parse_name_section(&source, &section));
+            }
+        }
+    }
+    entries.retain(|entry| pattern.is_match(&entry.names) || pattern.is_match(&entry.description));
+    entries.sort_by(|a, b| {
+        a.names
+            .cmp(&b.names)
+            .then(section_order(&a.section).cmp(&section_order(&b.section)))
+            .then(a.section.cmp(&b.section))
+    });
+    entries.dedup();
+    Ok(entries)
+}
+
+/// Searches the manual pages for the keyword (a case-insensitive regular expression) and
+/// lists the matching pages.
+pub fn apropos<Output: Write>(keyword: &str, output: &mut Output) -> Result<()> {
+    let pattern = RegexBuilder::new(keyword).case_insensitive(true).build()?;
+    let entries = search_man_pages_in(&get_man_path(), &pattern)?;
+    if entries.is_empty() {
+        return Err(Error::ProviderError(format!(
+            "No manual pages for `{keyword}`"
+        )));
+    }
+    for entry in entries {
+        writeln!(
+            output,
+            "{} {} {}  {}",
+            FOUND_EMOTICON.magenta(),
+            entry.names.green().bold(),
+            format!("({})", entry.section).cyan(),
+            entry.description.white().italic()
+        )?;
+    }
     Ok(())
 }
+
+/// Extracts the section with the given name (e.g. `OPTIONS`) from the manual page.
+///
+/// Section headers are expected to be unindented lines, possibly styled with escape sequences.
+pub fn extract_section(page: &str, name: &str) -> Option<String> {
+    let mut section: Option<Vec<&str>> = None;
+    for line in page.lines() {
+        let is_header = !line.is_empty() && !line.starts_with(char::is_whitespace);
+        if let Some(ref mut lines) = section {
+            if is_header {
+                break;
+            }
+            lines.push(line);
+        } else if is_header && sanitize(line).trim().eq_ignore_ascii_case(name) {
+            section = Some(Vec::new());
+        }
+    }
+    section.map(|lines| lines.join("\n").trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::test_dir::TestDir;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    #[test]
+    fn test_extract_section() {
+        let page = "NAME\n       ls - list directory contents\n\nOPTIONS\n       -a, --all\n              \
+                    do not ignore entries starting with .\n\nAUTHOR\n       Written by someone.\n";
+        assert_eq!(
+            Some("       -a, --all\n              do not ignore entries starting with ."),
+            extract_section(page, "options").as_deref()
+        );
+        assert_eq!(None, extract_section(page, "EXAMPLES"));
+        assert_eq!(
+            Some("       $ ls -la"),
+            extract_section("\x1b[1mEXAMPLES\x1b[0m\n       $ ls -la\n", "EXAMPLES").as_deref()
+        );
+    }
+
+    #[test]
+    fn test_find_man_pages() -> Result<()> {
+        let dir = TestDir::new("man")?;
+        fs::create_dir_all(dir.join("man1"))?;
+        fs::create_dir_all(dir.join("man3"))?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b".TH LS 1\n.SH NAME\nls \\- list directory contents\n")?;
+        fs::write(dir.join("man1").join("ls.1.gz"), encoder.finish()?)?;
+        fs::write(dir.join("man3").join("ls.3p"), ".TH LS 3P\n")?;
+        fs::write(dir.join("man1").join("dir.1"), ".so man1/ls.1\n")?;
+
+        let dirs = vec![dir.to_path_buf()];
+        let pages = find_man_pages_in(&dirs, "ls", None);
+        assert_eq!(
+            vec![
+                ManPage {
+                    name: String::from("ls"),
+                    section: String::from("1"),
+                    path: dir.join("man1").join("ls.1.gz"),
+                },
+                ManPage {
+                    name: String::from("ls"),
+                    section: String::from("3p"),
+                    path: dir.join("man3").join("ls.3p"),
+                },
+            ],
+            pages
+        );
+        assert_eq!(1, find_man_pages_in(&dirs, "ls", Some("3")).len());
+        assert!(find_man_pages_in(&dirs, "../ls", None).is_empty());
+
+        let redirect = find_man_pages_in(&dirs, "dir", None);
+        let source = redirect
+            .first()
+            .map(read_man_page)
+            .transpose()?
+            .unwrap_or_default();
+        assert_eq!(
+            Some("       ls - list directory contents"),
+            extract_section(&render_roff(&source, false, DEFAULT_WIDTH), "NAME").as_deref()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_man_query() {
+        assert_eq!((String::from("ls"), None), parse_man_query("ls"));
+        assert_eq!(
+            (String::from("printf"), Some(String::from("3"))),
+            parse_man_query("printf.3")
+        );
+        assert_eq!(
+            (String::from("crontab"), Some(String::from("5"))),
+            parse_man_query("crontab(5)")
+        );
+        assert_eq!(
+            (String::from("git-commit"), None),
+            parse_man_query("git commit")
+        );
+        assert_eq!((String::from("node.js"), None), parse_man_query("node.js"));
+    }
+
+    #[test]
+    fn test_search_man_pages() -> Result<()> {
+        let dir = TestDir::new("apropos")?;
+        let whatis_dir = dir.join("bsd");
+        fs::create_dir_all(dir.join("man1"))?;
+        fs::create_dir_all(dir.join("man5"))?;
+        fs::create_dir_all(&whatis_dir)?;
+        fs::write(
+            dir.join("man1").join("crontab.1"),
+            ".TH CRONTAB 1\n.SH NAME\ncrontab \\- maintain crontab files\n.SH SYNOPSIS\n",
+        )?;
+        fs::write(
+            dir.join("man5").join("crontab.5"),
+            ".Dd 2024\n.Sh NAME\n.Nm crontab\n.Nd tables for driving cron\n.Sh DESCRIPTION\n",
+        )?;
+        fs::write(dir.join("man1").join("cron.1"), ".so man5/crontab.5\n")?;
+        fs::write(
+            whatis_dir.join(WHATIS_FILE),
+            "cron(8) - daemon to execute scheduled commands\n",
+        )?;
+
+        let pattern = RegexBuilder::new("CRON").case_insensitive(true).build()?;
+        assert_eq!(
+            vec![
+                ManEntry {
+                    names: String::from("cron"),
+                    section: String::from("8"),
+                    description: String::from("daemon to execute scheduled commands"),
+                },
+                ManEntry {
+                    names: String::from("crontab"),
+                    section: String::from("1"),
+                    description: String::from("maintain crontab files"),
+                },
+                ManEntry {
+                    names: String::from("crontab"),
+                    section: String::from("5"),
+                    description: String::from("tables for driving cron"),
+                },
+            ],
+            search_man_pages_in(&[dir.to_path_buf(), whatis_dir], &pattern)?
+        );
+        Ok(())
+    }
+}
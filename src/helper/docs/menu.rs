@@ -0,0 +1,346 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::helper::docs::registry::ProviderRegistry;
+use crate::helper::docs::HelpPage;
+use colored::*;
+use console::{Key, Term};
+use dialoguer::theme::Theme;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Once};
+use std::thread;
+use std::time::Duration;
+
+/// Frames of the spinner that is shown while the page is being fetched.
+const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Interval of redrawing the spinners.
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Name of the threads that fetch the pages in the background.
+const PREFETCH_THREAD_NAME: &str = "prefetch";
+
+/// Availability of the page in a provider.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Availability {
+    /// The page is being fetched.
+    Pending,
+    /// The page is available.
+    Available,
+    /// Only a stale copy of the page is available (e.g. when offline).
+    Stale,
+    /// The page cannot be fetched.
+    Failed(ErrorKind),
+}
+
+impl Availability {
+    /// Returns the availability for the result of fetching the page.
+    pub fn from_result(result: &Result<HelpPage>) -> Self {
+        match result {
+            Ok(page) if page.stale_age.is_some() => Self::Stale,
+            Ok(_) => Self::Available,
+            Err(e) => Self::Failed(e.kind()),
+        }
+    }
+
+    /// Returns the marker that is shown next to the menu entry.
+    fn marker(self, frame: usize) -> String {
+        match self {
+            Self::Pending => SPINNER_FRAMES[frame % SPINNER_FRAMES.len()]
+                .cyan()
+                .to_string(),
+            Self::Available => "available".green().to_string(),
+            Self::Stale => "cached".yellow().to_string(),
+            Self::Failed(ErrorKind::NotFound) => "no page".red().to_string(),
+            Self::Failed(kind) => kind.to_string().yellow().to_string(),
+        }
+    }
+}
+
+/// State of the menu that is shared with the spinner thread.
+#[derive(Debug)]
+struct MenuState {
+    /// Index of the active entry.
+    active: usize,
+    /// Availability of the pages (`None` for the entries that are not providers, e.g. "Exit").
+    availability: Vec<Option<Availability>>,
+    /// Frame of the spinner.
+    frame: usize,
+    /// Number of the lines that are drawn.
+    drawn_lines: usize,
+}
+
+/// Menu of the providers that shows the availability of the pages.
+///
+/// The pages of all providers are fetched concurrently in the background when the menu is
+/// created. The entries show a spinner until the page is fetched and a marker afterwards, so
+/// the providers without a page can be spotted before picking them. The providers of the
+/// registry keep the fetched pages in memory, so picking an available entry does not fetch the
+/// page again. A panicking provider is marked as failed without printing the panic message over
+/// the menu.
+pub struct ProviderMenu {
+    /// Titles of the entries.
+    items: Vec<String>,
+    /// Shared state of the menu.
+    state: Arc<Mutex<MenuState>>,
+}
+
+impl ProviderMenu {
+    /// Creates the menu and starts fetching the pages of the given query.
+    ///
+    /// The given items (e.g. "Exit") are appended after the providers.
+    pub fn new(registry: Arc<ProviderRegistry>, query: &str, extra_items: &[&str]) -> Self {
+        let mut items = registry
+            .iter()
+            .map(|provider| provider.title())
+            .collect::<Vec<_>>();
+        let mut availability = vec![Some(Availability::Pending); items.len()];
+        items.extend(extra_items.iter().map(|item| item.to_string()));
+        availability.resize(items.len(), None);
+        let state = Arc::new(Mutex::new(MenuState {
+            active: 0,
+            availability,
+            frame: 0,
+            drawn_lines: 0,
+        }));
+        silence_prefetch_panics();
+        for index in 0..registry.iter().count() {
+            let registry = Arc::clone(&registry);
+            let shared = Arc::clone(&state);
+            let query = query.to_string();
+            let spawned = thread::Builder::new()
+                .name(PREFETCH_THREAD_NAME.to_string())
+                .spawn(move || {
+                    let Some(provider) = registry.get_index(index) else {
+                        return;
+                    };
+                    let availability =
+                        panic::catch_unwind(AssertUnwindSafe(|| provider.fetch(&query)))
+                            .map_or(Availability::Failed(ErrorKind::Other), |result| {
+                                Availability::from_result(&result)
+                            });
+                    if let Ok(mut state) = shared.lock() {
+                        state.availability[index] = Some(availability);
+                    }
+                });
+            if spawned.is_err() {
+                if let Ok(mut state) = state.lock() {
+                    state.availability[index] = Some(Availability::Failed(ErrorKind::Other));
+                }
+            }
+        }
+        Self { items, state }
+    }
+
+    /// Returns the availability of the page in the provider at the given index.
+    pub fn availability(&self, index: usize) -> Option<Availability> {
+        self.state
+            .lock()
+            .ok()
+            .and_then(|state| state.availability.get(index).copied().flatten())
+    }
+
+    /// Updates the availability of the page in the provider at the given index with the result of fetching it.
+    pub fn update(&self, index: usize, result: &Result<HelpPage>) {
+        if let Ok(mut state) = self.state.lock() {
+            if let Some(availability @ Some(_)) = state.availability.get_mut(index) {
+                *availability = Some(Availability::from_result(result));
+            }
+        }
+    }
+
+    /// Shows the menu and returns the index of the picked entry, or `None` if it is cancelled.
+    ///
+    /// A provider cannot be picked while its page is being fetched, so the menu stays responsive
+    /// (e.g. it can be cancelled) while a provider is slow.
+    pub fn interact(
+        &self,
+        theme: &(dyn Theme + Sync),
+        prompt: &str,
+        default: usize,
+        term: &Term,
+    ) -> Result<Option<usize>> {
+        if let Ok(mut state) = self.state.lock() {
+            state.active = default.min(self.items.len().saturating_sub(1));
+            state.drawn_lines = 0;
+        }
+        term.hide_cursor()?;
+        let done = AtomicBool::new(false);
+        let result = thread::scope(|scope| {
+            self.draw(theme, prompt, term)?;
+            scope.spawn(|| {
+                // Redraw once more after the last page is fetched to show its marker.
+                let mut was_pending = true;
+                while !done.load(Ordering::Relaxed) {
+                    thread::sleep(SPINNER_INTERVAL);
+                    let pending = self.is_pending();
+                    if !pending && !was_pending {
+                        continue;
+                    }
+                    was_pending = pending;
+                    if let Ok(mut state) = self.state.lock() {
+                        state.frame += 1;
+                    }
+                    let _ = self.draw(theme, prompt, term);
+                }
+            });
+            let result = self.read_selection(theme, prompt, term);
+            done.store(true, Ordering::Relaxed);
+            result
+        });
+        term.show_cursor()?;
+        let selection = result?;
+        self.clear(term)?;
+        if let Some(item) = selection.and_then(|index| self.items.get(index)) {
+            let mut line = String::new();
+            theme
+                .format_select_prompt_selection(&mut line, prompt, item)
+                .map_err(|e| Error::ProviderError(e.to_string()))?;
+            term.write_line(&line)?;
+        }
+        Ok(selection)
+    }
+
+    /// Reads the keys until an entry is picked or the menu is cancelled.
+    fn read_selection(
+        &self,
+        theme: &(dyn Theme + Sync),
+        prompt: &str,
+        term: &Term,
+    ) -> Result<Option<usize>> {
+        loop {
+            let key = term.read_key()?;
+            let Ok(mut state) = self.state.lock() else {
+                return Ok(None);
+            };
+            let count = self.items.len();
+            match key {
+                Key::ArrowDown | Key::Tab | Key::Char('j') => {
+                    state.active = (state.active + 1) % count;
+                }
+                Key::ArrowUp | Key::BackTab | Key::Char('k') => {
+                    state.active = (state.active + count - 1) % count;
+                }
+                Key::Home => state.active = 0,
+                Key::End => state.active = count - 1,
+                Key::Escape | Key::Char('q') | Key::CtrlC => return Ok(None),
+                Key::Enter | Key::Char(' ') => {
+                    if state.availability[state.active] != Some(Availability::Pending) {
+                        return Ok(Some(state.active));
+                    }
+                    continue;
+                }
+                _ => continue,
+            }
+            drop(state);
+            self.draw(theme, prompt, term)?;
+        }
+    }
+
+    /// Returns `true` if any of the pages is being fetched.
+    fn is_pending(&self) -> bool {
+        self.state
+            .lock()
+            .is_ok_and(|state| state.availability.contains(&Some(Availability::Pending)))
+    }
+
+    /// Draws the menu, replacing the previously drawn one.
+    fn draw(&self, theme: &(dyn Theme + Sync), prompt: &str, term: &Term) -> Result<()> {
+        let Ok(mut state) = self.state.lock() else {
+            return Ok(());
+        };
+        let mut menu = String::new();
+        let format_error = |e: std::fmt::Error| Error::ProviderError(e.to_string());
+        theme
+            .format_select_prompt(&mut menu, prompt)
+            .map_err(format_error)?;
+        for (index, item) in self.items.iter().enumerate() {
+            let text = match state.availability[index] {
+                Some(availability) => format!("{item}  {}", availability.marker(state.frame)),
+                None => item.to_string(),
+            };
+            menu.push('\n');
+            theme
+                .format_select_prompt_item(&mut menu, &text, index == state.active)
+                .map_err(format_error)?;
+        }
+        term.clear_last_lines(state.drawn_lines)?;
+        term.write_line(&menu)?;
+        state.drawn_lines = menu.lines().count();
+        Ok(())
+    }
+
+    /// Clears the drawn menu.
+    fn clear(&self, term: &Term) -> Result<()> {
+        if let Ok(mut state) = self.state.lock() {
+            term.clear_last_lines(state.drawn_lines)?;
+            state.drawn_lines = 0;
+        }
+        Ok(())
+    }
+}
+
+/// Installs a panic hook that does not print the panics of the prefetch threads.
+///
+/// The panics are reported in the menu instead, while the other threads keep the previous hook.
+fn silence_prefetch_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() != Some(PREFETCH_THREAD_NAME) {
+                hook(info);
+            }
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::docs::command::CommandProvider;
+    use crate::helper::docs::HelpProvider;
+    use pretty_assertions::assert_eq;
+    use std::time::Instant;
+
+    struct PanickingProvider;
+
+    impl HelpProvider for PanickingProvider {
+        fn name(&self) -> &str {
+            "panic"
+        }
+
+        fn fetch(&self, _query: &str) -> Result<HelpPage> {
+            panic!("provider bug")
+        }
+    }
+
+    #[test]
+    fn test_provider_menu() {
+        let mut registry = ProviderRegistry::default();
+        registry.register(CommandProvider::new("echo", "echo"));
+        registry.register(CommandProvider::new("fail", "false"));
+        registry.register(PanickingProvider);
+        let menu = ProviderMenu::new(Arc::new(registry), "ls", &["Exit"]);
+        let start = Instant::now();
+        while menu.is_pending() && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(SPINNER_INTERVAL);
+        }
+        assert_eq!(Some(Availability::Available), menu.availability(0));
+        assert_eq!(
+            Some(Availability::Failed(ErrorKind::Other)),
+            menu.availability(1)
+        );
+        assert_eq!(
+            Some(Availability::Failed(ErrorKind::Other)),
+            menu.availability(2)
+        );
+        assert_eq!(None, menu.availability(3));
+        menu.update(1, &Err(Error::TimeoutError(5)));
+        assert_eq!(
+            Some(Availability::Failed(ErrorKind::Timeout)),
+            menu.availability(1)
+        );
+        menu.update(3, &Err(Error::TimeoutError(5)));
+        assert_eq!(None, menu.availability(3));
+    }
+}
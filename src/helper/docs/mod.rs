@@ -1,176 +1,541 @@
 /// Man page helper.
 pub mod man;
 
+/// Local cheat sheet helper.
+pub mod cheat;
 /// Cheat sheet helper.
 pub mod cheat_sh;
 /// cheat helper.
 pub mod cheatsheets;
 /// eg page helper.
 pub mod eg;
+/// tldr page helper.
+pub mod tldr;
+
+/// On-disk cache of the pages.
+pub mod cache;
+/// In-memory cached provider.
+pub mod cached;
+/// Local command provider.
+pub mod command;
+/// Local file provider.
+pub mod file;
+/// HTTP provider.
+pub mod http;
+/// GNU info page helper.
+pub mod info;
+/// Provider menu with page availability.
+pub mod menu;
+/// Personal notes.
+pub mod notes;
+/// External provider plugins.
+pub mod plugin;
+/// Provider registry.
+pub mod registry;
+/// Page renderer.
+pub mod render;
+/// Roff and mdoc renderer.
+pub mod roff;
+/// Local mirror server.
+pub mod serve;
+/// Local mirror synchronization.
+pub mod sync;
 
 use crate::config::Config;
-use crate::error::{Error, Result};
-use crate::helper::docs::cheat_sh::CheatDotSh;
-use crate::helper::docs::cheatsheets::Cheatsheets;
-use crate::helper::docs::eg::Eg;
-use crate::helper::docs::man::show_man_page;
+use crate::error::{Error, ErrorKind, Result};
+use crate::helper::args::parser::sanitize;
+use crate::helper::args::{FAIL_EMOTICON, FOUND_EMOTICON};
+use crate::helper::docs::cheat_sh::{search_query, CHEAT_SH_PROVIDER};
+use crate::helper::docs::man::{extract_section, MAN_EXAMPLES_PROVIDER, MAN_PROVIDER};
+use crate::helper::docs::menu::ProviderMenu;
+use crate::helper::docs::registry::ProviderRegistry;
+use crate::helper::docs::render::render_page;
+use colored::*;
 use console::{style, Style, Term};
 use dialoguer::theme::ColorfulTheme;
-use dialoguer::Select;
-use std::io::Write;
+use dialoguer::{Input, Select};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{self, IsTerminal, Write};
 use std::process::{Command, Stdio};
-/// The `HelpProvider` trait defines essential methods for fetching help content related to commands from a provider.
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Menu entry for searching the cheat.sh pages.
+const SEARCH_CHEAT_SH_ITEM: &str = "Search the cheat.sh pages";
+
+/// Menu entry for showing the pages of all providers at once.
+const SHOW_EVERYTHING_ITEM: &str = "Show everything";
+
+/// Providers that are shown with the `--examples` flag.
+pub const EXAMPLES_PROVIDERS: &[&str] = &[MAN_EXAMPLES_PROVIDER, "eg", "cheat.sh"];
+
+/// Providers that are merged into a single page with the `--all` flag.
 ///
-/// Each provider that implements this trait should provide a default URL used to retrieve the command help content.
-/// However, it also allows specifying a custom URL to override the default one.
+/// Only the SYNOPSIS section of the man page is included.
+pub const ALL_PROVIDERS: &[&str] = &[MAN_PROVIDER, "eg", "cheat.sh", "cheatsheets"];
+
+/// Format of the page contents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    /// Plain text (possibly with ANSI escape sequences).
+    #[default]
+    Plain,
+    /// Markdown.
+    Markdown,
+    /// HTML.
+    Html,
+}
+
+/// A help page fetched from a provider.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HelpPage {
+    /// Name of the provider that the page is fetched from.
+    pub provider: String,
+    /// Contents of the page.
+    pub content: String,
+    /// Format of the contents.
+    pub format: ContentFormat,
+    /// Age of the page if it is a stale copy from the cache (e.g. when offline).
+    pub stale_age: Option<Duration>,
+}
+
+/// The `HelpProvider` trait defines the essential methods for fetching help content related to commands from a provider.
 ///
-/// This trait is generic and not tied to any specific command help system such as man pages or POSIX documentation,
-/// instead it relies on the implementation to define how to fetch and mark up the content.
+/// This trait is not tied to any specific help system or transport. A provider might fetch the page over HTTP
+/// (e.g. [`cheat.sh`](http://cheat.sh)), read it from a local file, capture the output of a local command
+/// (e.g. `man`) or wrap another provider (e.g. for caching). Providers must be thread-safe since the pages are
+/// fetched concurrently.
 ///
 /// # Methods
 ///
-/// - `url`: Returns the default URL of the provider.
-/// - `build_req`: Uses the given command and URL to create an HTTP GET request.
-/// - `err_handle`: Handles possible request errors, such as a non-existent command page on the provider.
-/// - `fetch`: Attempts to retrieve the command page from the provider, optionally from a custom URL.
+/// - `name`: Returns the name of the provider (e.g. `cheat.sh`).
+/// - `title`: Returns the title of the provider that is shown in the menu.
+/// - `fetch`: Fetches the page for the given query (e.g. the command name).
+/// - `variants`: Returns the variants of the page that the user can pick from (e.g. the sections of a manual page).
 ///
 /// # Example
 ///
 /// An implementation could be created for a provider that supplies help pages in Markdown format.
-/// The `url` method would return the base URL of this provider.
-/// The `build_request` method could construct a GET request for `{base_url}/{command}.md`.
-/// The `handle_error` could interpret a 404 status code as 'Command page not found'.
-/// The `fetch` would handle fetching the specified command page using the constructed request.
-pub trait HelpProvider {
-    /// Return the default provider URL.
-    fn url(&self) -> &'static str;
-
-    /// Builds an HTTP request using the given `cmd` and `url`.
+/// The `name` method would return a short identifier such as `markdown-pages`.
+/// The `fetch` method would retrieve `{base_url}/{query}.md` and return it as a [`HelpPage`],
+/// returning [`Error::NotFoundError`](crate::error::Error::NotFoundError) if there is no page for the query.
+pub trait HelpProvider: Send + Sync {
+    /// Returns the name of the provider.
+    fn name(&self) -> &str;
+
+    /// Returns the title of the provider that is shown in the menu.
+    fn title(&self) -> String {
+        format!("Show the {} page", self.name())
+    }
+
+    /// Fetches the page from the provider.
     ///
     /// # Parameters
     ///
-    /// - `cmd`: The name of the command to be included in the request.
-    /// - `url`: The root URL.
+    /// - `query`: The query (e.g. name of the command) for which the page should be fetched.
     ///
-    /// # Returns
-    /// This method returns a new `RequestBuilder` configured with the `GET` method and the formatted URL.
-    fn build_request(
-        &self,
-        cmd: &str,
-        url: &str,
-    ) -> ureq::RequestBuilder<ureq::typestate::WithoutBody>;
-
-    /// Handle the request error.
-    /// aka return a custom message if the error means that **provider** doesn't have a page for the command.
-    fn handle_error(&self, e: ureq::Error) -> Error {
-        if matches!(e, ureq::Error::StatusCode(_)) {
-            Error::ProviderError(
-                "Unknown topic, This topic/command might has no page in this provider yet."
-                    .to_string(),
-            )
+    /// # Errors
+    ///
+    /// This method will return an error if the fetch operation fails or the provider has no page for the query.
+    fn fetch(&self, query: &str) -> Result<HelpPage>;
+
+    /// Returns the variants of the page for the given query (e.g. `printf(1)` and `printf(3)`).
+    ///
+    /// Each variant is a query that can be passed to [`fetch`](HelpProvider::fetch). If there are
+    /// multiple variants, the user is asked to pick one of them.
+    fn variants(&self, _query: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Renders the page and shows it using the user selected pager or writes it directly into the output.
+pub fn show_page<Output: Write>(
+    page: &HelpPage,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let content = render_page(page);
+    if let Some(pager) = config.pager_command.as_ref() {
+        let mut process = if cfg!(target_os = "windows") {
+            Command::new("cmd")
+                .args(["/C", pager])
+                .stdin(Stdio::piped())
+                .spawn()
         } else {
-            Error::from(Box::new(e))
+            Command::new("sh")
+                .args(["-c", pager])
+                .stdin(Stdio::piped())
+                .spawn()
+        }?;
+        if let Some(stdin) = process.stdin.as_mut() {
+            writeln!(stdin, "{}", content)?;
+            process.wait()?;
         }
+    } else {
+        writeln!(output, "{}", content)?;
     }
+    Ok(())
+}
 
-    /// **The default** fetch implementation.
-    ///
-    /// This method attempts to retrieve the specified command page from the given provider.
-    /// If a `custom_url` is provided, this URL is used instead of the default URL.
-    /// The method will return the content of the command page if the fetch operation is successful.
-    #[inline(always)]
-    fn _fetch(&self, cmd: &str, custom_url: &Option<String>) -> Result<String> {
-        let url = {
-            if let Some(u) = custom_url {
-                u.as_str()
-            } else {
-                self.url()
+/// Shows documentation/usage help about the given command.
+///
+/// The menu is built from the providers in the [`ProviderRegistry`] and the pages of all
+/// providers are fetched in the background while the menu is shown (see [`ProviderMenu`]).
+pub fn get_docs_help<Output: Write>(cmd: &str, config: &Config, output: &mut Output) -> Result<()> {
+    let registry = Arc::new(ProviderRegistry::from_config(config)?);
+    let mut extra_items = Vec::new();
+    if registry.get(CHEAT_SH_PROVIDER).is_some() {
+        extra_items.push(SEARCH_CHEAT_SH_ITEM);
+    }
+    extra_items.extend([SHOW_EVERYTHING_ITEM, "Exit"]);
+    let menu = ProviderMenu::new(Arc::clone(&registry), cmd, &extra_items);
+    let provider_count = registry.iter().count();
+    let mut selection = Some(0);
+    loop {
+        selection = menu.interact(
+            &get_selection_theme(),
+            "Select operation",
+            selection.unwrap_or_default(),
+            &Term::stderr(),
+        )?;
+        let Some(index) = selection else {
+            return Ok(());
+        };
+        let Some(provider) = registry.get_index(index) else {
+            match extra_items.get(index - provider_count).copied() {
+                Some(SEARCH_CHEAT_SH_ITEM) => search_cheat_sh(cmd, &registry, config, output)?,
+                Some(SHOW_EVERYTHING_ITEM) => match combine_pages(cmd, &registry) {
+                    Ok(page) => show_page(&page, config, output)?,
+                    Err(e) => report_error("all", &e),
+                },
+                _ => return Ok(()),
             }
+            continue;
         };
-        let response = self.build_request(cmd, url).call();
-
-        let response = response.map_err(|e| self.handle_error(e));
+        let variants = provider.variants(cmd).unwrap_or_default();
+        let query = if variants.len() > 1 {
+            let variant = Select::with_theme(&get_selection_theme())
+                .with_prompt("Select page")
+                .default(0)
+                .items(&variants)
+                .interact_on_opt(&Term::stderr())?;
+            match variant.and_then(|index| variants.get(index)) {
+                Some(variant) => variant.as_str(),
+                None => continue,
+            }
+        } else {
+            cmd
+        };
+        show_with_retry(provider, query, config, output, |result| {
+            if query == cmd {
+                menu.update(index, result);
+            }
+        })?;
+    }
+}
 
-        match response {
-            Ok(mut response) => response
-                .body_mut()
-                .read_to_string()
-                .map_err(|e| Error::from(Box::new(e))),
-            Err(e) => Err(e),
+/// Fetches the page from the provider and shows it.
+///
+/// If the page cannot be fetched, the error is reported and the user can retry unless the
+/// provider has no page. The given function is called with the result of each fetch.
+fn show_with_retry<Output: Write>(
+    provider: &dyn HelpProvider,
+    query: &str,
+    config: &Config,
+    output: &mut Output,
+    on_result: impl Fn(&Result<HelpPage>),
+) -> Result<()> {
+    loop {
+        let result = provider.fetch(query);
+        on_result(&result);
+        match result {
+            Ok(page) => return show_page(&page, config, output),
+            Err(e) => {
+                report_error(provider.name(), &e);
+                if e.kind() == ErrorKind::NotFound || !ask_retry()? {
+                    return Ok(());
+                }
+            }
         }
     }
+}
 
-    /// Fetches the command page from the provider.
-    ///
-    /// # Parameters
-    ///
-    /// - `cmd`: The name of the command for which the page should be fetched.
-    /// - `custom_url`: Optional parameter that, if supplied, specifies a custom URL from which to fetch the command page.
-    ///
-    /// # Returns
-    ///
-    /// This method returns a Result type. On successful fetch, it contains a `String` with the content of the fetched page.
-    /// In case of failure, it contains an error that provides further details about the issue encountered during the fetch operation.
-    ///
-    /// # Errors
-    ///
-    /// This method will return an error if the fetch operation fails.
-    fn fetch(&self, cmd: &str, custom_url: &Option<String>) -> Result<String> {
-        self._fetch(cmd, custom_url)
+/// Asks for a keyword and shows the cheat.sh pages of the command that match it.
+fn search_cheat_sh<Output: Write>(
+    cmd: &str,
+    registry: &ProviderRegistry,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let Some(provider) = registry.get(CHEAT_SH_PROVIDER) else {
+        return Ok(());
+    };
+    let keyword: String = Input::with_theme(&get_selection_theme())
+        .with_prompt("Search for")
+        .interact_text_on(&Term::stderr())?;
+    show_with_retry(
+        provider,
+        &search_query(cmd, keyword.trim()),
+        config,
+        output,
+        |_| {},
+    )
+}
+
+/// Asks the user whether to fetch the page again after a failure.
+fn ask_retry() -> Result<bool> {
+    let selection = Select::with_theme(&get_selection_theme())
+        .with_prompt("Fetching the page failed")
+        .default(0)
+        .items(["Retry", "Back to menu"])
+        .interact_on_opt(&Term::stderr())?;
+    Ok(selection == Some(0))
+}
+
+/// Shows the examples of the given command from the providers in [`EXAMPLES_PROVIDERS`] at once.
+///
+/// Providers without a page for the command are skipped.
+pub fn get_docs_examples<Output: Write>(
+    cmd: &str,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let registry = ProviderRegistry::from_config(config)?;
+    let mut sections = Vec::new();
+    for provider in EXAMPLES_PROVIDERS
+        .iter()
+        .filter_map(|name| registry.get(name))
+    {
+        if let Ok(page) = provider.fetch(cmd) {
+            sections.push(render_section(provider, &page));
+        }
+    }
+    if sections.is_empty() {
+        return Err(Error::NotFoundError(format!(
+            "No examples found for `{cmd}`"
+        )));
     }
+    let page = HelpPage {
+        provider: String::from("examples"),
+        content: sections.join("\n\n"),
+        ..Default::default()
+    };
+    show_page(&page, config, output)
 }
 
-/// Shows documentation/usage help about the given command.
-pub fn get_docs_help<Output: Write>(cmd: &str, config: &Config, output: &mut Output) -> Result<()> {
-    const MAN_PAGE: usize = 0;
-    const CHEAT_SHEET: usize = 1;
-    const EG_PAGE: usize = 2;
-    const CHEATSHEETS: usize = 3;
-    let menu_options = [
-        "Show man page",
-        "Show cheat.sh page",
-        "Show the eg page",
-        "Show the cheatsheet page",
-        "Exit",
-    ];
-    let mut selection = Some(MAN_PAGE);
-    loop {
-        selection = Select::with_theme(&get_selection_theme())
-            .with_prompt("Select operation")
-            .default(selection.unwrap_or_default())
-            .items(&menu_options)
-            .interact_on_opt(&Term::stderr())?;
-        if let Some(MAN_PAGE) = selection {
-            show_man_page(&config.man_command, cmd)?
+/// Shows the pages of the given command from the providers in [`ALL_PROVIDERS`] as a single page.
+pub fn get_docs_all<Output: Write>(cmd: &str, config: &Config, output: &mut Output) -> Result<()> {
+    let registry = ProviderRegistry::from_config(config)?;
+    show_page(&combine_pages(cmd, &registry)?, config, output)
+}
+
+/// Merges the pages of the given command from the providers in [`ALL_PROVIDERS`].
+///
+/// Each section is headed with the name of the provider and the example commands that are
+/// already shown in the previous sections are removed. Providers without a page are skipped.
+fn combine_pages(cmd: &str, registry: &ProviderRegistry) -> Result<HelpPage> {
+    let mut seen = HashSet::new();
+    let mut sections = Vec::new();
+    for provider in ALL_PROVIDERS.iter().filter_map(|name| registry.get(name)) {
+        let Ok(page) = provider.fetch(cmd) else {
+            continue;
+        };
+        let content = if provider.name() == MAN_PROVIDER {
+            match extract_section(&page.content, "SYNOPSIS") {
+                Some(synopsis) => format!("{}\n{synopsis}", "SYNOPSIS".bold()),
+                None => continue,
+            }
         } else {
-            let page = match selection {
-                Some(CHEAT_SHEET) => CheatDotSh.fetch(cmd, &config.cheat_sh_url)?,
-                Some(EG_PAGE) => Eg.fetch(cmd, &config.eg_url)?,
-                Some(CHEATSHEETS) => Cheatsheets.fetch(cmd, &config.cheatsheets_url)?,
-                _ => return Ok(()),
-            };
-            // Show the page using the user selected pager or write it directly into the output
-            if let Some(pager) = config.pager_command.as_ref() {
-                let mut process = if cfg!(target_os = "windows") {
-                    Command::new("cmd")
-                        .args(["/C", pager])
-                        .stdin(Stdio::piped())
-                        .spawn()
-                } else {
-                    Command::new("sh")
-                        .args(["-c", pager])
-                        .stdin(Stdio::piped())
-                        .spawn()
-                }?;
-                if let Some(stdin) = process.stdin.as_mut() {
-                    writeln!(stdin, "{}", page)?;
-                    process.wait()?;
+            render_page(&page)
+        };
+        let content = dedup_examples(cmd, &content, &mut seen);
+        if content.trim().is_empty() {
+            continue;
+        }
+        sections.push(format!(
+            "{} {}\n{}",
+            FOUND_EMOTICON.magenta(),
+            format!("{}:", provider.name()).cyan().bold(),
+            content.trim_end()
+        ));
+    }
+    if sections.is_empty() {
+        return Err(Error::NotFoundError(format!("No page found for `{cmd}`")));
+    }
+    Ok(HelpPage {
+        provider: String::from("all"),
+        content: sections.join("\n\n"),
+        ..Default::default()
+    })
+}
+
+/// Removes the example commands that are in the given set from the rendered page and adds the
+/// remaining ones to the set.
+///
+/// Example commands are the lines that start with the command name (optionally after a `$ `
+/// prompt). Paragraphs that only consist of removed examples and their descriptions are dropped.
+fn dedup_examples(cmd: &str, content: &str, seen: &mut HashSet<String>) -> String {
+    let program = cmd.split_whitespace().next().unwrap_or(cmd);
+    let normalize = |line: &str| {
+        let line = sanitize(line);
+        let line = line.trim().trim_matches('`');
+        let line = line.strip_prefix("$ ").unwrap_or(line);
+        let is_example = line
+            .strip_prefix(program)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace));
+        is_example.then(|| line.split_whitespace().collect::<Vec<_>>().join(" "))
+    };
+    let mut paragraphs = Vec::new();
+    let mut paragraph = Vec::new();
+    let mut lines = content.lines().peekable();
+    while let Some(line) = lines.next() {
+        let is_blank = sanitize(line).trim().is_empty();
+        if !is_blank {
+            paragraph.push(line);
+        }
+        if !(is_blank || lines.peek().is_none()) || paragraph.is_empty() {
+            continue;
+        }
+        let mut has_examples = false;
+        let mut kept = Vec::new();
+        let mut kept_examples = false;
+        for line in paragraph.drain(..) {
+            match normalize(line) {
+                Some(example) => {
+                    has_examples = true;
+                    if seen.insert(example) {
+                        kept_examples = true;
+                        kept.push(line);
+                    }
+                }
+                None => kept.push(line),
+            }
+        }
+        if !has_examples || kept_examples {
+            paragraphs.push(kept.join("\n"));
+        }
+    }
+    paragraphs.join("\n\n")
+}
+
+/// Returns `true` if the user can be prompted (i.e. stdin and stderr are terminals).
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stderr().is_terminal()
+}
+
+/// Renders the page with a header that shows the name of the provider.
+fn render_section(provider: &dyn HelpProvider, page: &HelpPage) -> String {
+    format!(
+        "{} {}\n{}",
+        FOUND_EMOTICON.magenta(),
+        format!("{}:", provider.name()).cyan().bold(),
+        render_page(page).trim_end()
+    )
+}
+
+/// Writes the pages of the given command from the given providers into the output without
+/// prompting (e.g. for scripts and pipes).
+///
+/// All providers are used if none is given. Providers without a page for the command are
+/// reported and skipped. An error is returned if no page is found or if any of the given
+/// providers fails, so that scripts can detect it.
+pub fn print_docs<Output: Write>(
+    cmd: &str,
+    providers: &[String],
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let registry = ProviderRegistry::from_config(config)?;
+    let mut found = false;
+    let mut failed = Vec::new();
+    for provider in select_providers(&registry, providers)? {
+        match provider.fetch(cmd) {
+            Ok(page) => {
+                if found {
+                    writeln!(output)?;
                 }
-            } else {
-                writeln!(output, "{}", page)?;
+                writeln!(output, "{}", render_section(provider, &page))?;
+                found = true;
+            }
+            Err(e) => {
+                report_error(provider.name(), &e);
+                failed.push(provider.name());
             }
         }
     }
+    if !found {
+        return Err(Error::NotFoundError(format!("No page found for `{cmd}`")));
+    }
+    if !providers.is_empty() && !failed.is_empty() {
+        return Err(Error::ProviderError(format!(
+            "Failed to fetch the page of `{cmd}` from {}",
+            failed.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Shows the page of the given command from the first provider in the fallback chain that has one.
+///
+/// The chain is the given providers, or the `fallback` providers in the configuration if none is
+/// given, or all providers otherwise. Providers without a page are reported and skipped.
+pub fn get_docs_first<Output: Write>(
+    cmd: &str,
+    providers: &[String],
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let registry = ProviderRegistry::from_config(config)?;
+    let chain = if providers.is_empty() {
+        &config.fallback
+    } else {
+        providers
+    };
+    for provider in select_providers(&registry, chain)? {
+        match provider.fetch(cmd) {
+            Ok(page) => {
+                eprintln!(
+                    "{} {}",
+                    FOUND_EMOTICON.magenta(),
+                    format!("{}:", provider.name()).cyan().bold()
+                );
+                return show_page(&page, config, output);
+            }
+            Err(e) => report_error(provider.name(), &e),
+        }
+    }
+    Err(Error::NotFoundError(format!("No page found for `{cmd}`")))
+}
+
+/// Returns the providers with the given names in the given order, or all providers if no name is given.
+fn select_providers<'a>(
+    registry: &'a ProviderRegistry,
+    names: &[String],
+) -> Result<Vec<&'a dyn HelpProvider>> {
+    if names.is_empty() {
+        return Ok(registry.iter().collect());
+    }
+    names
+        .iter()
+        .map(|name| {
+            registry
+                .get(name)
+                .ok_or_else(|| Error::ProviderError(format!("Unknown provider: `{name}`")))
+        })
+        .collect()
+}
+
+/// Reports the error of fetching the page from the provider along with the kind of the error.
+fn report_error(provider: &str, error: &Error) {
+    eprintln!(
+        "{} {} {} {}",
+        FAIL_EMOTICON.magenta(),
+        format!("{provider}:").red().bold(),
+        format!("[{}]", error.kind()).yellow(),
+        error.to_string().white().italic()
+    );
 }
 
 /// Returns the theme for selection prompt.
@@ -196,3 +561,89 @@ fn get_selection_theme() -> ColorfulTheme {
         unpicked_item_prefix: style(" ".to_string()).for_stderr(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ProviderConfig, ProviderKind};
+    use pretty_assertions::assert_eq;
+
+    /// Returns the configuration of a provider that runs the given command.
+    fn provider(name: &str, command: &str) -> ProviderConfig {
+        ProviderConfig {
+            name: name.to_string(),
+            kind: ProviderKind::Command(command.to_string()),
+            headers: Default::default(),
+            not_found: None,
+            format: ContentFormat::Plain,
+        }
+    }
+
+    #[test]
+    fn test_print_docs() -> Result<()> {
+        let config = Config {
+            providers: vec![
+                provider("internal", "echo internal docs for {cmd}"),
+                provider("broken", "false"),
+            ],
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        print_docs("ls", &[String::from("internal")], &config, &mut output)?;
+        assert_eq!(
+            format!("{FOUND_EMOTICON} internal:\ninternal docs for ls\n"),
+            String::from_utf8_lossy(&output)
+        );
+        assert!(print_docs("ls", &[String::from("unknown")], &config, &mut output).is_err());
+        output.clear();
+        let providers = [String::from("internal"), String::from("broken")];
+        assert!(print_docs("ls", &providers, &config, &mut output).is_err());
+        assert_eq!(
+            format!("{FOUND_EMOTICON} internal:\ninternal docs for ls\n"),
+            String::from_utf8_lossy(&output)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_docs_first() -> Result<()> {
+        let config = Config {
+            providers: vec![
+                ProviderConfig {
+                    not_found: Some(String::from("^missing")),
+                    ..provider("team", "echo missing {cmd}")
+                },
+                provider("internal", "echo internal docs for {cmd}"),
+            ],
+            fallback: vec![String::from("team"), String::from("internal")],
+            pager_command: None,
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        get_docs_first("ls", &[], &config, &mut output)?;
+        assert_eq!("internal docs for ls\n\n", String::from_utf8_lossy(&output));
+        assert!(get_docs_first("ls", &[String::from("team")], &config, &mut output).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedup_examples() {
+        let mut seen = HashSet::new();
+        assert_eq!(
+            "# list all files\nls -la\n\n# sort by size\nls -S",
+            dedup_examples(
+                "ls",
+                "# list all files\nls -la\n\n# sort by size\nls -S\n",
+                &mut seen
+            )
+        );
+        assert_eq!(
+            "List by time:\n\n    ls -t\n\nlsblk is another command",
+            dedup_examples(
+                "ls",
+                "# long listing\n$ ls  -la\n\nList by time:\n\n    ls -t\n    `ls -S`\n\nlsblk is another command",
+                &mut seen
+            )
+        );
+    }
+}
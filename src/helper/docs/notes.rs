@@ -0,0 +1,244 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::helper::args::{CHECK_EMOTICON, FOUND_EMOTICON};
+use crate::helper::docs::file::{is_inner_path, FileProvider};
+use crate::helper::docs::render::render_page;
+use crate::helper::docs::{ContentFormat, HelpPage, HelpProvider};
+use colored::*;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Name of the provider.
+pub const NOTE_PROVIDER: &str = "note";
+
+/// File extension of the notes.
+const NOTE_EXTENSION: &str = "md";
+
+/// Returns the directory of the personal notes (e.g. `<data_dir>/halp/notes`).
+pub fn get_notes_dir(config: &Config) -> Result<PathBuf> {
+    match config.notes_dir {
+        Some(ref dir) => Ok(dir.to_path_buf()),
+        None => Ok(Config::get_data_dir()?.join("notes")),
+    }
+}
+
+/// Returns the path of the note for the given command (e.g. `<notes_dir>/git/commit.md`).
+///
+/// Returns an error if the path would be outside of the directory (e.g. `../x` or `/etc/x`).
+pub fn get_note_path(dir: &Path, cmd: &str) -> Result<PathBuf> {
+    let topic = cmd.split_whitespace().collect::<Vec<_>>().join("/");
+    if topic.is_empty() || !is_inner_path(&topic) {
+        return Err(Error::ProviderError(format!("Invalid command: `{cmd}`")));
+    }
+    Ok(dir.join(format!("{topic}.{NOTE_EXTENSION}")))
+}
+
+/// Provider for the personal notes of the commands.
+#[derive(Clone, Debug)]
+pub struct NoteProvider {
+    /// Provider that reads the notes from the directory.
+    file: FileProvider,
+}
+
+impl NoteProvider {
+    /// Creates a new provider for the notes in the given directory.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            file: FileProvider::new(NOTE_PROVIDER, dir)
+                .extension(NOTE_EXTENSION)
+                .format(ContentFormat::Markdown),
+        }
+    }
+}
+
+impl HelpProvider for NoteProvider {
+    fn name(&self) -> &str {
+        NOTE_PROVIDER
+    }
+
+    fn title(&self) -> String {
+        String::from("Show the personal note")
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        self.file
+            .fetch(&query.split_whitespace().collect::<Vec<_>>().join("/"))
+            .map_err(|e| match e {
+                Error::NotFoundError(_) => {
+                    Error::NotFoundError(format!("No note for `{query}` (see `halp note`)"))
+                }
+                e => e,
+            })
+    }
+}
+
+/// Returns the editor command (`$VISUAL`, `$EDITOR` or `vi`).
+fn get_editor() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| {
+            String::from(if cfg!(target_os = "windows") {
+                "notepad"
+            } else {
+                "vi"
+            })
+        })
+}
+
+/// Opens the note of the given command in the editor.
+///
+/// The note is created with a heading if it does not exist.
+pub fn edit_note<Output: Write>(cmd: &str, config: &Config, output: &mut Output) -> Result<()> {
+    let path = get_note_path(&get_notes_dir(config)?, cmd)?;
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, format!("# {cmd}\n\n"))?;
+    }
+    let editor = get_editor();
+    writeln!(
+        output,
+        "{}  {} {}",
+        CHECK_EMOTICON.magenta(),
+        "editing".green().bold(),
+        format!("{:?}", path).white().italic()
+    )?;
+    let status = if cfg!(target_os = "windows") {
+        Command::new("cmd")
+            .args(["/C", &editor])
+            .arg(&path)
+            .status()
+    } else {
+        Command::new("sh")
+            .args(["-c", &format!("{editor} \"$1\""), "sh"])
+            .arg(&path)
+            .status()
+    }?;
+    if !status.success() {
+        return Err(Error::ProviderError(format!("Editor `{editor}` failed")));
+    }
+    Ok(())
+}
+
+/// Writes the note of the given command into the output if it exists.
+pub fn show_note<Output: Write>(cmd: &str, config: &Config, output: &mut Output) -> Result<()> {
+    let provider = NoteProvider::new(get_notes_dir(config)?);
+    if let Ok(page) = provider.fetch(cmd) {
+        writeln!(
+            output,
+            "{} {}",
+            FOUND_EMOTICON.magenta(),
+            "personal note:".cyan().bold()
+        )?;
+        writeln!(output, "{}", render_page(&page))?;
+    }
+    Ok(())
+}
+
+/// Collects the notes in the directory recursively.
+fn find_notes(dir: &Path, notes: &mut Vec<PathBuf>) -> Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_notes(&path, notes)?;
+        } else if path.extension().and_then(|v| v.to_str()) == Some(NOTE_EXTENSION) {
+            notes.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Exports the notes into the given directory in the `cheatsheets` format.
+///
+/// Each note is written without the extension (e.g. `<dir>/git/commit`) with a front matter
+/// that marks it as Markdown.
+///
+/// Returns the number of exported notes.
+pub fn export_notes_to(notes_dir: &Path, dir: &Path) -> Result<usize> {
+    let mut notes = Vec::new();
+    find_notes(notes_dir, &mut notes)?;
+    for note in &notes {
+        let Ok(topic) = note
+            .with_extension("")
+            .strip_prefix(notes_dir)
+            .map(PathBuf::from)
+        else {
+            continue;
+        };
+        let path = dir.join(topic);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(
+            path,
+            format!(
+                "---\nsyntax: markdown\ntags: [ notes ]\n---\n{}",
+                fs::read_to_string(note)?
+            ),
+        )?;
+    }
+    Ok(notes.len())
+}
+
+/// Exports the notes into the given directory.
+pub fn export_notes<Output: Write>(dir: &Path, config: &Config, output: &mut Output) -> Result<()> {
+    let count = export_notes_to(&get_notes_dir(config)?, dir)?;
+    writeln!(
+        output,
+        "{} {} {} notes to {}",
+        FOUND_EMOTICON.magenta(),
+        "exported".green().bold(),
+        count,
+        format!("{:?}", dir).white().italic()
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_notes() -> Result<()> {
+        let dir = TestDir::new("notes")?;
+        let notes_dir = dir.join("notes");
+        let path = get_note_path(&notes_dir, "git commit")?;
+        assert_eq!(notes_dir.join("git").join("commit.md"), path);
+        assert!(get_note_path(&notes_dir, "../x").is_err());
+        assert!(get_note_path(&notes_dir, "/etc/x").is_err());
+        assert!(get_note_path(&notes_dir, " ").is_err());
+        fs::create_dir_all(notes_dir.join("git"))?;
+        fs::write(&path, "# git commit\n")?;
+
+        let provider = NoteProvider::new(notes_dir.to_path_buf());
+        assert_eq!(
+            HelpPage {
+                provider: String::from(NOTE_PROVIDER),
+                content: String::from("# git commit\n"),
+                format: ContentFormat::Markdown,
+                ..Default::default()
+            },
+            provider.fetch("git commit")?
+        );
+        assert!(provider.fetch("tar").is_err());
+
+        let export_dir = dir.join("export");
+        assert_eq!(1, export_notes_to(&notes_dir, &export_dir)?);
+        assert_eq!(
+            "---\nsyntax: markdown\ntags: [ notes ]\n---\n# git commit\n",
+            fs::read_to_string(export_dir.join("git").join("commit"))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,497 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::helper::args::{get_timeout, FAIL_EMOTICON, FOUND_EMOTICON};
+use crate::helper::docs::{report_error, ContentFormat, HelpPage, HelpProvider};
+use crate::helper::executables::find_executables;
+use colored::*;
+use process_control::{ChildExt, Control};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Prefix of the plugin executables.
+pub const PLUGIN_PREFIX: &str = "halp-provider-";
+
+/// Version of the plugin protocol.
+pub const PLUGIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Kind of the plugin errors that mean that the plugin has no page for the query.
+const PLUGIN_NOT_FOUND: &str = "not_found";
+
+/// Request that is sent to the plugin.
+#[derive(Debug, Serialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+enum PluginRequest<'a> {
+    /// Describe the plugin.
+    Describe,
+    /// Fetch the page for the query.
+    Fetch {
+        /// Query (e.g. name of the command).
+        query: &'a str,
+    },
+    /// Search the pages.
+    Search {
+        /// Search query.
+        query: &'a str,
+    },
+}
+
+/// Envelope of the request with the protocol version.
+#[derive(Debug, Serialize)]
+struct VersionedRequest<'a> {
+    /// Protocol version.
+    version: u32,
+    /// Request.
+    #[serde(flatten)]
+    request: PluginRequest<'a>,
+}
+
+/// Error that is reported by the plugin.
+#[derive(Debug, Deserialize)]
+struct PluginError {
+    /// Kind of the error (e.g. `not_found`).
+    #[serde(default)]
+    kind: Option<String>,
+    /// Error message.
+    message: String,
+}
+
+/// Response to the `describe` request.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+struct PluginDescription {
+    /// Name of the provider.
+    name: String,
+    /// Title of the provider that is shown in the menu.
+    title: Option<String>,
+    /// Format of the pages.
+    #[serde(default)]
+    format: ContentFormat,
+}
+
+/// Description of a plugin that is cached until the executable changes.
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedDescription {
+    /// Modification time of the executable (seconds since the Unix epoch).
+    modified: u64,
+    /// Size of the executable.
+    size: u64,
+    /// Description of the plugin.
+    description: PluginDescription,
+}
+
+/// Response to the `fetch` request.
+#[derive(Debug, Deserialize)]
+struct PluginPage {
+    /// Contents of the page.
+    content: String,
+    /// Format of the page (defaults to the format in the description).
+    format: Option<ContentFormat>,
+}
+
+/// Response to the `search` request.
+#[derive(Debug, Deserialize)]
+struct PluginSearchResults {
+    /// Search results.
+    results: Vec<SearchResult>,
+}
+
+/// Result of a search in the plugin.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SearchResult {
+    /// Query that can be used for fetching the page.
+    pub query: String,
+    /// Description of the page.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Provider that is backed by a plugin executable.
+///
+/// A plugin is an executable (e.g. `halp-provider-wiki` on `PATH`) that speaks a small JSON
+/// protocol over stdin/stdout. For each request, the plugin is started, a single JSON object is
+/// written to its stdin and a single JSON object is read from its stdout.
+///
+/// Requests:
+///
+/// ```json
+/// {"version": 1, "method": "describe"}
+/// {"version": 1, "method": "fetch", "query": "tar"}
+/// {"version": 1, "method": "search", "query": "archive"}
+/// ```
+///
+/// Responses:
+///
+/// ```json
+/// {"version": 1, "name": "wiki", "title": "Show the wiki page", "format": "markdown"}
+/// {"version": 1, "content": "# tar\n...", "format": "markdown"}
+/// {"version": 1, "results": [{"query": "tar", "description": "Archiving utility"}]}
+/// {"version": 1, "error": {"kind": "not_found", "message": "No page for tar"}}
+/// ```
+///
+/// `title` and `format` are optional. `format` is one of `plain`, `markdown` or `html`.
+#[derive(Clone, Debug)]
+pub struct PluginProvider {
+    /// Path or name of the executable.
+    program: PathBuf,
+    /// Timeout for the requests in seconds.
+    timeout: u64,
+    /// Name of the provider.
+    name: String,
+    /// Title of the provider.
+    title: Option<String>,
+    /// Default format of the pages.
+    format: ContentFormat,
+}
+
+impl PluginProvider {
+    /// Creates the plugin with the given description.
+    fn new(program: &Path, timeout: u64, description: PluginDescription) -> Self {
+        Self {
+            program: program.to_path_buf(),
+            timeout,
+            name: description.name,
+            title: description.title,
+            format: description.format,
+        }
+    }
+
+    /// Starts the plugin and loads its description.
+    pub fn load(program: &Path, timeout: u64) -> Result<Self> {
+        let mut plugin = Self::new(
+            program,
+            timeout,
+            PluginDescription {
+                name: String::new(),
+                title: None,
+                format: ContentFormat::default(),
+            },
+        );
+        let description: PluginDescription = plugin.call(PluginRequest::Describe)?;
+        plugin.name = description.name;
+        plugin.title = description.title;
+        plugin.format = description.format;
+        Ok(plugin)
+    }
+
+    /// Returns the description of the plugin.
+    fn description(&self) -> PluginDescription {
+        PluginDescription {
+            name: self.name.to_string(),
+            title: self.title.clone(),
+            format: self.format,
+        }
+    }
+
+    /// Returns the path or name of the executable.
+    pub fn program(&self) -> &Path {
+        &self.program
+    }
+
+    /// Searches the pages of the plugin.
+    pub fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let response: PluginSearchResults = self.call(PluginRequest::Search { query })?;
+        Ok(response.results)
+    }
+
+    /// Sends the request to the plugin and parses the response.
+    fn call<T: DeserializeOwned>(&self, request: PluginRequest) -> Result<T> {
+        let request = serde_json::to_string(&VersionedRequest {
+            version: PLUGIN_PROTOCOL_VERSION,
+            request,
+        })?;
+        let mut child = Command::new(&self.program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "{request}")?;
+        }
+        let output = child
+            .controlled_with_output()
+            .time_limit(Duration::from_secs(self.timeout))
+            .terminate_for_timeout()
+            .wait()?
+            .ok_or(Error::TimeoutError(self.timeout))?;
+        let plugin = self.program.display();
+        if !output.status.success() {
+            return Err(Error::ProviderError(format!(
+                "Plugin {plugin} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let response: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        let version = response.get("version").and_then(|v| v.as_u64());
+        if version != Some(u64::from(PLUGIN_PROTOCOL_VERSION)) {
+            return Err(Error::ProviderError(format!(
+                "Plugin {plugin} uses an unsupported protocol version: {version:?} \
+                 (expected {PLUGIN_PROTOCOL_VERSION})"
+            )));
+        }
+        if let Some(error) = response.get("error") {
+            let error: PluginError = serde_json::from_value(error.clone())?;
+            return Err(match error.kind.as_deref() {
+                Some(PLUGIN_NOT_FOUND) => Error::NotFoundError(error.message),
+                Some(kind) => Error::ProviderError(format!("{} ({kind})", error.message)),
+                None => Error::ProviderError(error.message),
+            });
+        }
+        Ok(serde_json::from_value(response)?)
+    }
+}
+
+impl HelpProvider for PluginProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn title(&self) -> String {
+        self.title
+            .clone()
+            .unwrap_or_else(|| format!("Show the {} page", self.name))
+    }
+
+    fn fetch(&self, query: &str) -> Result<HelpPage> {
+        let page: PluginPage = self.call(PluginRequest::Fetch { query })?;
+        Ok(HelpPage {
+            provider: self.name.to_string(),
+            content: page.content,
+            format: page.format.unwrap_or(self.format),
+            ..Default::default()
+        })
+    }
+}
+
+/// Returns the plugin executables that are listed in the configuration and found in `PATH`.
+pub fn find_plugins(config: &Config) -> Vec<PathBuf> {
+    let mut plugins = config.plugins.clone();
+    for executable in find_executables() {
+        if executable.starts_with(PLUGIN_PREFIX)
+            && !plugins
+                .iter()
+                .any(|plugin| plugin.file_name().and_then(|v| v.to_str()) == Some(&executable))
+        {
+            plugins.push(PathBuf::from(executable));
+        }
+    }
+    plugins
+}
+
+/// Returns the path of the cached plugin descriptions (e.g. `~/.cache/halp/plugins.json`).
+fn get_description_cache() -> Option<PathBuf> {
+    Some(
+        dirs::cache_dir()?
+            .join(env!("CARGO_PKG_NAME"))
+            .join("plugins.json"),
+    )
+}
+
+/// Returns the modification time and size of the executable (searched in `PATH` if needed).
+fn get_executable_stamp(program: &Path) -> Option<(u64, u64)> {
+    let path = if program.components().count() > 1 {
+        program.to_path_buf()
+    } else {
+        env::split_paths(&env::var_os("PATH")?)
+            .map(|dir| dir.join(program))
+            .find(|path| path.is_file())?
+    };
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((modified.as_secs(), metadata.len()))
+}
+
+/// Loads the plugins and caches their descriptions in the given file.
+///
+/// A plugin is only started if its executable changed since its description was cached.
+/// Plugins that cannot be loaded are reported and skipped.
+fn load_plugins_cached(
+    programs: Vec<PathBuf>,
+    timeout: u64,
+    cache_path: Option<&Path>,
+) -> Vec<PluginProvider> {
+    let mut cache: HashMap<PathBuf, CachedDescription> = cache_path
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default();
+    let mut changed = false;
+    let mut plugins = Vec::new();
+    for program in programs {
+        let stamp = get_executable_stamp(&program);
+        let cached = cache
+            .get(&program)
+            .filter(|cached| Some((cached.modified, cached.size)) == stamp);
+        if let Some(cached) = cached {
+            plugins.push(PluginProvider::new(
+                &program,
+                timeout,
+                cached.description.clone(),
+            ));
+            continue;
+        }
+        match PluginProvider::load(&program, timeout) {
+            Ok(plugin) => {
+                if let Some((modified, size)) = stamp {
+                    let description = plugin.description();
+                    cache.insert(
+                        program,
+                        CachedDescription {
+                            modified,
+                            size,
+                            description,
+                        },
+                    );
+                    changed = true;
+                }
+                plugins.push(plugin);
+            }
+            Err(e) => report_error(&program.display().to_string(), &e),
+        }
+    }
+    if let Some(path) = cache_path.filter(|_| changed) {
+        if let Err(e) = write_description_cache(path, &cache) {
+            report_error("plugins", &e);
+        }
+    }
+    plugins
+}
+
+/// Writes the cached descriptions of the plugins.
+fn write_description_cache(path: &Path, cache: &HashMap<PathBuf, CachedDescription>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(cache)?)?;
+    Ok(())
+}
+
+/// Loads the plugins.
+///
+/// The descriptions of the plugins are cached, so the plugins are not started every time the
+/// providers are loaded. Plugins that cannot be loaded are reported and skipped.
+pub fn load_plugins(config: &Config) -> Vec<PluginProvider> {
+    load_plugins_cached(
+        find_plugins(config),
+        get_timeout(config),
+        get_description_cache().as_deref(),
+    )
+}
+
+/// Lists the available plugins.
+pub fn list_plugins<Output: Write>(config: &Config, output: &mut Output) -> Result<()> {
+    for plugin in load_plugins(config) {
+        writeln!(
+            output,
+            "{} {} {}",
+            FOUND_EMOTICON.magenta(),
+            plugin.name().green().bold(),
+            format!("{:?}", plugin.program()).white().italic()
+        )?;
+    }
+    Ok(())
+}
+
+/// Searches the pages of all plugins.
+pub fn search_plugins<Output: Write>(
+    query: &str,
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    for plugin in load_plugins(config) {
+        match plugin.search(query) {
+            Ok(results) => {
+                for result in results {
+                    writeln!(
+                        output,
+                        "{}  {}  {}",
+                        plugin.name().cyan().bold(),
+                        result.query.green().bold(),
+                        result.description.unwrap_or_default().white().italic()
+                    )?;
+                }
+            }
+            Err(e) => {
+                writeln!(
+                    output,
+                    "{}      {} {}: {}",
+                    FAIL_EMOTICON.magenta(),
+                    "fail".red().bold(),
+                    plugin.name(),
+                    e.to_string().white().italic()
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_protocol() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = TestDir::new("plugin")?;
+        let program = dir.join(format!("{PLUGIN_PREFIX}test"));
+        fs::write(
+            &program,
+            r##"#!/bin/sh
+read -r request
+case "$request" in
+  *'"describe"'*) echo >> "$0.describe"; echo '{"version":1,"name":"test","format":"markdown"}' ;;
+  *'"fetch"'*'"tar"'*) echo '{"version":1,"content":"# tar"}' ;;
+  *'"fetch"'*) echo '{"version":1,"error":{"kind":"not_found","message":"no page"}}' ;;
+  *'"search"'*) echo '{"version":1,"results":[{"query":"tar","description":"archiver"}]}' ;;
+  *) echo '{"version":2}' ;;
+esac
+"##,
+        )?;
+        fs::set_permissions(&program, fs::Permissions::from_mode(0o755))?;
+        let plugin = PluginProvider::load(&program, 5)?;
+        assert_eq!("test", plugin.name());
+        assert_eq!("Show the test page", plugin.title());
+        assert_eq!(
+            HelpPage {
+                provider: String::from("test"),
+                content: String::from("# tar"),
+                format: ContentFormat::Markdown,
+                ..Default::default()
+            },
+            plugin.fetch("tar")?
+        );
+        assert_eq!(
+            Some(String::from("no page")),
+            plugin.fetch("ls").err().map(|e| e.to_string())
+        );
+        assert_eq!(
+            vec![SearchResult {
+                query: String::from("tar"),
+                description: Some(String::from("archiver")),
+            }],
+            plugin.search("archive")?
+        );
+
+        let cache = dir.join("plugins.json");
+        for _ in 0..2 {
+            let plugins = load_plugins_cached(vec![program.to_path_buf()], 5, Some(&cache));
+            assert_eq!(
+                vec!["test"],
+                plugins.iter().map(|p| p.name()).collect::<Vec<_>>()
+            );
+        }
+        // Described once by `load` and once before caching the description.
+        let describe = dir.join(format!("{PLUGIN_PREFIX}test.describe"));
+        assert_eq!(2, fs::read_to_string(describe)?.lines().count());
+        Ok(())
+    }
+}
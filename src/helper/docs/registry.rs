@@ -0,0 +1,307 @@
+use crate::config::{Config, ProviderConfig, ProviderKind};
+use crate::error::{Error, Result};
+use crate::helper::args::get_timeout;
+use crate::helper::docs::cache::PageCache;
+use crate::helper::docs::cached::CachedProvider;
+use crate::helper::docs::cheat::CheatProvider;
+use crate::helper::docs::cheat_sh::CheatSh;
+use crate::helper::docs::command::CommandProvider;
+use crate::helper::docs::file::FileProvider;
+use crate::helper::docs::http::HttpProvider;
+use crate::helper::docs::info::InfoProvider;
+use crate::helper::docs::man::{ManExamplesProvider, ManProvider};
+use crate::helper::docs::notes::{get_notes_dir, NoteProvider};
+use crate::helper::docs::plugin::load_plugins;
+use crate::helper::docs::sync::get_provider_mirror;
+use crate::helper::docs::tldr::Tldr;
+use crate::helper::docs::{cheatsheets, eg, report_error, ContentFormat, HelpProvider};
+use regex::Regex;
+use std::sync::Arc;
+
+/// Registry of the help providers.
+///
+/// The order of the registration determines the order in the menu.
+#[derive(Default)]
+pub struct ProviderRegistry {
+    /// Registered providers.
+    providers: Vec<Arc<dyn HelpProvider>>,
+}
+
+impl ProviderRegistry {
+    /// Creates a registry with the built-in providers, user-defined providers and plugins from the given configuration.
+    ///
+    /// The local cheat sheets and personal notes are shown first if they exist.
+    /// User-defined providers and plugins are registered after the built-in ones. User-defined
+    /// providers replace the provider with the same name, while the plugins with a name that is
+    /// already taken are reported and skipped. The pages of the providers are cached in memory.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut registry = Self::default();
+        let cache = PageCache::from_config(config);
+        if !config.cheatpaths.is_empty() {
+            registry.register(CheatProvider::new(config.cheatpaths.clone()));
+        }
+        if let Some(dir) = get_notes_dir(config).ok().filter(|dir| dir.is_dir()) {
+            registry.register(NoteProvider::new(dir));
+        }
+        let man = registry.register(ManProvider::new(&config.man_command));
+        registry.register(ManExamplesProvider::new(
+            man,
+            config.man_synopsis.unwrap_or(false),
+        ));
+        if let Some(info) = Some(InfoProvider::from_env()).filter(InfoProvider::has_dirs) {
+            registry.register(info);
+        }
+        let mirror = |provider: &str| get_provider_mirror(config, provider);
+        let timeout = get_timeout(config);
+        let mut cheat_sh = CheatSh::from_config(config);
+        cheat_sh.http = cheat_sh.http.cache(cache.clone());
+        cheat_sh.mirror = mirror("cheat.sh");
+        registry.register(cheat_sh);
+        registry.register(
+            eg::provider(config.eg_url.as_deref())
+                .timeout(timeout)
+                .cache(cache.clone())
+                .mirror(mirror("eg")),
+        );
+        registry.register(
+            cheatsheets::provider(config.cheatsheets_url.as_deref())
+                .timeout(timeout)
+                .cache(cache.clone())
+                .mirror(mirror("cheatsheets")),
+        );
+        let mut tldr = Tldr::from_config(config);
+        tldr.http = tldr.http.cache(cache.clone()).mirror(mirror("tldr"));
+        registry.register(tldr);
+        for provider in &config.providers {
+            registry.register_config(provider, cache.clone(), timeout)?;
+        }
+        for plugin in load_plugins(config) {
+            let name = plugin.name().to_string();
+            if let Err(e) = registry.try_register(plugin) {
+                report_error(&name, &e);
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Registers a user-defined provider.
+    ///
+    /// The "not found" pattern is applied to the `url` and `command` providers and the cache and
+    /// the request timeout (in seconds) are used for the `url` providers.
+    pub fn register_config(
+        &mut self,
+        provider: &ProviderConfig,
+        cache: Option<PageCache>,
+        timeout: u64,
+    ) -> Result<()> {
+        let not_found = provider.not_found.as_deref().map(Regex::new).transpose()?;
+        match provider.kind {
+            ProviderKind::Url(ref url) => {
+                let mut http = HttpProvider::new(&provider.name, url)
+                    .format(provider.format)
+                    .timeout(timeout)
+                    .cache(cache);
+                for (name, value) in &provider.headers {
+                    http = http.header(name, value);
+                }
+                if let Some(not_found) = not_found {
+                    http = http.not_found(not_found);
+                }
+                self.register(http);
+            }
+            ProviderKind::Command(ref command) => {
+                let mut command =
+                    CommandProvider::new(&provider.name, command).format(provider.format);
+                if let Some(not_found) = not_found {
+                    command = command.not_found(not_found);
+                }
+                self.register(command);
+            }
+            ProviderKind::Directory(ref dir) => {
+                let extension = match provider.format {
+                    ContentFormat::Plain => "txt",
+                    ContentFormat::Markdown => "md",
+                    ContentFormat::Html => "html",
+                };
+                self.register(
+                    FileProvider::new(&provider.name, dir.to_path_buf())
+                        .format(provider.format)
+                        .extension(extension),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Registers a provider.
+    ///
+    /// A previously registered provider with the same name is replaced. The returned handle
+    /// shares the in-memory cache of the registered provider.
+    pub fn register<Provider: HelpProvider + 'static>(
+        &mut self,
+        provider: Provider,
+    ) -> Arc<dyn HelpProvider> {
+        let provider: Arc<dyn HelpProvider> = Arc::new(CachedProvider::new(Box::new(provider)));
+        match self
+            .providers
+            .iter_mut()
+            .find(|registered| registered.name() == provider.name())
+        {
+            Some(registered) => *registered = Arc::clone(&provider),
+            None => self.providers.push(Arc::clone(&provider)),
+        }
+        provider
+    }
+
+    /// Registers a provider unless a provider with the same name is already registered.
+    pub fn try_register<Provider: HelpProvider + 'static>(
+        &mut self,
+        provider: Provider,
+    ) -> Result<Arc<dyn HelpProvider>> {
+        if self.get(provider.name()).is_some() {
+            return Err(Error::ProviderError(format!(
+                "A provider named `{}` is already registered",
+                provider.name()
+            )));
+        }
+        Ok(self.register(provider))
+    }
+
+    /// Returns the provider with the given name.
+    pub fn get(&self, name: &str) -> Option<&dyn HelpProvider> {
+        self.iter().find(|provider| provider.name() == name)
+    }
+
+    /// Returns the provider at the given index.
+    pub fn get_index(&self, index: usize) -> Option<&dyn HelpProvider> {
+        self.providers.get(index).map(|provider| provider.as_ref())
+    }
+
+    /// Returns an iterator over the providers.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn HelpProvider> {
+        self.providers.iter().map(|provider| provider.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::helper::docs::command::CommandProvider;
+    use pretty_assertions::assert_eq;
+
+    /// Returns the names of the providers, without `info` if there is no info directory.
+    fn expected_names(names: &[&'static str]) -> Vec<&'static str> {
+        let info = InfoProvider::from_env().has_dirs();
+        names
+            .iter()
+            .copied()
+            .filter(|name| *name != "info" || info)
+            .collect()
+    }
+
+    #[test]
+    fn test_registry() -> Result<()> {
+        let mut registry = ProviderRegistry::from_config(&Config::default())?;
+        assert_eq!(
+            expected_names(&[
+                "man",
+                "man-examples",
+                "info",
+                "cheat.sh",
+                "eg",
+                "cheatsheets",
+                "tldr"
+            ]),
+            registry.iter().map(|p| p.name()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(String::from("Show the eg page")),
+            registry.get("eg").map(|p| p.title())
+        );
+        assert!(registry
+            .try_register(CommandProvider::new("man", "echo man"))
+            .is_err());
+        registry.register(CommandProvider::new("eg", "echo eg"));
+        registry.register(CommandProvider::new("echo", "echo"));
+        assert_eq!(
+            expected_names(&[
+                "man",
+                "man-examples",
+                "info",
+                "cheat.sh",
+                "eg",
+                "cheatsheets",
+                "tldr",
+                "echo"
+            ]),
+            registry.iter().map(|p| p.name()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(String::from("eg ls\n")),
+            registry
+                .get("eg")
+                .map(|p| p.fetch("ls"))
+                .transpose()?
+                .map(|page| page.content)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_config() -> Result<()> {
+        let config = Config {
+            providers: vec![
+                ProviderConfig {
+                    name: String::from("internal"),
+                    kind: ProviderKind::Command(String::from("echo internal docs for {cmd}")),
+                    headers: Default::default(),
+                    not_found: Some(String::from("docs for unknown")),
+                    format: ContentFormat::Plain,
+                },
+                ProviderConfig {
+                    name: String::from("wiki"),
+                    kind: ProviderKind::Url(String::from("https://wiki.example.com/{cmd}")),
+                    headers: Default::default(),
+                    not_found: None,
+                    format: ContentFormat::Html,
+                },
+            ],
+            ..Default::default()
+        };
+        let registry = ProviderRegistry::from_config(&config)?;
+        assert_eq!(
+            expected_names(&[
+                "man",
+                "man-examples",
+                "info",
+                "cheat.sh",
+                "eg",
+                "cheatsheets",
+                "tldr",
+                "internal",
+                "wiki"
+            ]),
+            registry.iter().map(|p| p.name()).collect::<Vec<_>>()
+        );
+        let provider = registry.get("internal");
+        assert_eq!(
+            Some(String::from("internal docs for ls\n")),
+            provider
+                .map(|p| p.fetch("ls"))
+                .transpose()?
+                .map(|page| page.content)
+        );
+        assert_eq!(Some(true), provider.map(|p| p.fetch("unknown").is_err()));
+
+        let config = Config {
+            providers: vec![ProviderConfig {
+                not_found: Some(String::from("(")),
+                ..config.providers[0].clone()
+            }],
+            ..Default::default()
+        };
+        assert!(ProviderRegistry::from_config(&config).is_err());
+        Ok(())
+    }
+}
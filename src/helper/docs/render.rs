@@ -0,0 +1,163 @@
+use crate::helper::args::FAIL_EMOTICON;
+use crate::helper::docs::cache::format_age;
+use crate::helper::docs::{ContentFormat, HelpPage};
+use colored::*;
+use regex::{Captures, Regex};
+
+/// Renders the page contents as terminal text based on the format of the page.
+///
+/// Stale pages (e.g. cached pages that are shown while offline) are headed with their age.
+pub fn render_page(page: &HelpPage) -> String {
+    let content = match page.format {
+        ContentFormat::Plain => page.content.to_string(),
+        ContentFormat::Markdown => render_markdown(&page.content),
+        ContentFormat::Html => html_to_text(&page.content),
+    };
+    match page.stale_age {
+        Some(age) => format!(
+            "{} {} {}\n\n{content}",
+            FAIL_EMOTICON.magenta(),
+            "offline, showing the cached page from".yellow().bold(),
+            format!("{} ago", format_age(age)).white().italic()
+        ),
+        None => content,
+    }
+}
+
+/// Renders the Markdown text with terminal styles.
+///
+/// Only the elements that are commonly used in the help pages are styled:
+/// headings, quotes, code blocks and inline code.
+pub fn render_markdown(text: &str) -> String {
+    let inline_code = Regex::new(r"`([^`]+)`").ok();
+    let mut in_code_block = false;
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        let rendered = if in_code_block {
+            line.green().to_string()
+        } else if line.starts_with('#') {
+            line.trim_start_matches('#')
+                .trim()
+                .magenta()
+                .bold()
+                .to_string()
+        } else if let Some(quote) = line.strip_prefix('>') {
+            quote.trim().italic().to_string()
+        } else if let Some(ref inline_code) = inline_code {
+            inline_code
+                .replace_all(line, |captures: &Captures| captures[1].green().to_string())
+                .to_string()
+        } else {
+            line.to_string()
+        };
+        lines.push(rendered);
+    }
+    lines.join("\n")
+}
+
+/// Converts the HTML document into plain text.
+pub fn html_to_text(html: &str) -> String {
+    let replacements = [
+        (r"(?is)<(script|style|head)\b.*?</(script|style|head)>", ""),
+        (r"(?i)<br\s*/?>", "\n"),
+        (r"(?i)<li\b[^>]*>", "\n- "),
+        (
+            r"(?i)</?(p|div|h[1-6]|pre|tr|ul|ol|table|section|article)\b[^>]*>",
+            "\n",
+        ),
+        (r"(?s)<[^>]*>", ""),
+    ];
+    let mut text = html.to_string();
+    for (pattern, replacement) in replacements {
+        if let Ok(regex) = Regex::new(pattern) {
+            text = regex.replace_all(&text, replacement).to_string();
+        }
+    }
+    let text = decode_html_entities(&text);
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim_end) {
+        // Collapse consecutive blank lines.
+        if line.trim().is_empty() && lines.last().map_or(true, |last| last.is_empty()) {
+            continue;
+        }
+        lines.push(if line.trim().is_empty() { "" } else { line });
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Decodes the common named and numeric HTML entities.
+fn decode_html_entities(text: &str) -> String {
+    let Ok(entity) = Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);") else {
+        return text.to_string();
+    };
+    entity
+        .replace_all(text, |captures: &Captures| {
+            let name = &captures[1];
+            let decoded = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| name.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| captures[0].to_string(), String::from)
+        })
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_render_markdown() {
+        assert_eq!(
+            format!(
+                "{}\n\n{}\n{}\n{}",
+                "tar".magenta().bold(),
+                "Archiving utility.".italic(),
+                "- Create an archive: ".to_string() + &"tar cf a.tar".green().to_string(),
+                "tar xf a.tar".green()
+            ),
+            render_markdown(
+                "# tar\n\n> Archiving utility.\n- Create an archive: `tar cf a.tar`\n```sh\ntar xf a.tar\n```"
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_stale_page() {
+        let page = HelpPage {
+            content: String::from("tar page"),
+            stale_age: Some(Duration::from_secs(7200)),
+            ..Default::default()
+        };
+        let rendered = render_page(&page);
+        assert!(rendered.contains(&format_age(Duration::from_secs(7200))));
+        assert!(rendered.ends_with("\n\ntar page"));
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            "Usage\n\nls [OPTION]... [FILE]...\n\n- -a <all> & more\n- -l",
+            html_to_text(
+                "<html><head><title>x</title></head><body><h1>Usage</h1><p>ls [OPTION]... [FILE]...</p>\
+                 <ul><li>-a &lt;all&gt; &amp;&#32;more</li><li>-l</li></ul></body></html>"
+            )
+        );
+    }
+}
@@ -0,0 +1,1536 @@
+use colored::*;
+use std::collections::HashMap;
+
+/// Default width of the rendered pages.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Default indentation of the paragraphs.
+const INDENT: usize = 7;
+
+/// Indentation of the subsection headings.
+const SUBSECTION_INDENT: usize = 3;
+
+/// Macros of `mdoc` that can be called inside a macro line (e.g. `.Op Fl a Ar file`).
+const MDOC_CALLABLE: &[&str] = &[
+    "Ad", "An", "Aq", "Ar", "At", "Bq", "Brq", "Bx", "Cd", "Cm", "Dq", "Dv", "Em", "Er", "Ev",
+    "Fa", "Fl", "Fn", "Ft", "Ic", "Li", "Lk", "Ms", "Mt", "Nm", "No", "Ns", "Oc", "Oo", "Op", "Pa",
+    "Pq", "Ql", "Qq", "Sq", "St", "Sx", "Sy", "Tn", "Ux", "Va", "Vt", "Xr",
+];
+
+/// Font of the text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Font {
+    /// Regular text.
+    #[default]
+    Regular,
+    /// Bold text (e.g. literal command names).
+    Bold,
+    /// Italic text (e.g. arguments), rendered as underlined text.
+    Italic,
+}
+
+impl Font {
+    /// Returns the font with the given name (e.g. `B`) or `None` for the previous font.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "B" | "3" | "BI" | "CB" => Some(Self::Bold),
+            "I" | "2" | "CI" => Some(Self::Italic),
+            "P" | "" => None,
+            _ => Some(Self::Regular),
+        }
+    }
+}
+
+/// A part of a word with a single font.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Segment {
+    /// Text of the segment.
+    text: String,
+    /// Font of the segment.
+    font: Font,
+    /// Whether the segment is attached to the previous one without a space.
+    glue: bool,
+}
+
+impl Segment {
+    /// Creates a new segment.
+    fn new(text: &str, font: Font, glue: bool) -> Self {
+        Self {
+            text: text.to_string(),
+            font,
+            glue,
+        }
+    }
+}
+
+/// What the next line of text is used for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Pending {
+    /// Tag of a tagged paragraph (e.g. after `.TP`).
+    Tag,
+    /// Heading with the given indentation (e.g. `.SH` without arguments).
+    Heading(usize),
+}
+
+/// Kind of an `mdoc` list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ListKind {
+    /// Items with a tag (`-tag`, `-hang`, `-ohang`, `-inset`, `-diag`).
+    Tag,
+    /// Items with a bullet.
+    Bullet,
+    /// Items with a dash.
+    Dash,
+    /// Numbered items.
+    Enum,
+    /// Items without a tag.
+    Item,
+    /// Items with columns.
+    Column,
+}
+
+/// State of an `mdoc` list (`.Bl`).
+#[derive(Clone, Debug)]
+struct List {
+    /// Kind of the list.
+    kind: ListKind,
+    /// Width of the tags.
+    width: usize,
+    /// Whether the items are not separated with blank lines.
+    compact: bool,
+    /// Number of the items.
+    count: usize,
+    /// Base indentation before the list.
+    base: usize,
+}
+
+/// State of an `mdoc` display block (`.Bd`).
+#[derive(Clone, Debug)]
+struct Display {
+    /// Fill mode before the block.
+    fill: bool,
+    /// Base indentation before the block.
+    base: usize,
+}
+
+/// Renderer for the `man` and `mdoc` macro packages of roff.
+///
+/// Only the subset of roff that is used by the manual pages is supported, the rest of the
+/// requests (e.g. page layout) are ignored.
+#[derive(Debug)]
+struct Renderer {
+    /// Whether to style the text with terminal escape sequences.
+    styled: bool,
+    /// Width of the page.
+    width: usize,
+    /// Rendered lines.
+    lines: Vec<String>,
+    /// Words of the current paragraph.
+    words: Vec<Segment>,
+    /// Tag of the current paragraph with its indentation.
+    tag: Option<(usize, Vec<Segment>)>,
+    /// Indentation of the current paragraph.
+    indent: usize,
+    /// Left margin.
+    base: usize,
+    /// Indentation of the tagged paragraphs.
+    hang: usize,
+    /// Margins that are saved by `.RS`.
+    margins: Vec<usize>,
+    /// Left margin before the last `.in`.
+    prev_base: usize,
+    /// Whether the lines are filled.
+    fill: bool,
+    /// Current font.
+    font: Font,
+    /// Previous font.
+    prev_font: Font,
+    /// Font of the next line (e.g. after `.B` without arguments).
+    next_font: Option<Font>,
+    /// What the next line of text is used for.
+    pending: Option<Pending>,
+    /// Defined strings (`.ds`).
+    strings: HashMap<String, String>,
+    /// Result of the last `.ie` condition.
+    last_condition: bool,
+    /// Nesting level of the skipped conditional blocks.
+    skip_depth: usize,
+    /// URL of the current `.UR` or `.MT` link.
+    link: Option<String>,
+    /// Name of the page (`.Nm`).
+    name: Option<String>,
+    /// Whether the current section is the synopsis.
+    synopsis: bool,
+    /// Number of the lines at the start of the current section (after its heading).
+    section_start: usize,
+    /// Whether the words of the `mdoc` macros are separated with spaces (`.Sm`).
+    spacing: bool,
+    /// Whether the next `mdoc` macro is the first one after disabling the spacing.
+    spacing_off: bool,
+    /// Open `mdoc` lists.
+    lists: Vec<List>,
+    /// Open `mdoc` displays.
+    displays: Vec<Display>,
+}
+
+impl Renderer {
+    /// Creates a new renderer.
+    fn new(styled: bool, width: usize) -> Self {
+        Self {
+            styled,
+            width,
+            lines: Vec::new(),
+            words: Vec::new(),
+            tag: None,
+            indent: INDENT,
+            base: INDENT,
+            hang: INDENT,
+            margins: Vec::new(),
+            prev_base: INDENT,
+            fill: true,
+            font: Font::Regular,
+            prev_font: Font::Regular,
+            next_font: None,
+            pending: None,
+            strings: HashMap::new(),
+            last_condition: false,
+            skip_depth: 0,
+            link: None,
+            name: None,
+            synopsis: false,
+            section_start: 0,
+            spacing: true,
+            spacing_off: false,
+            lists: Vec::new(),
+            displays: Vec::new(),
+        }
+    }
+
+    /// Renders the roff source.
+    fn render(mut self, source: &str) -> String {
+        let source = source.replace("\\\n", "");
+        let mut lines = source.lines();
+        while let Some(line) = lines.next() {
+            if self.skip_depth > 0 {
+                self.skip_depth += line.matches("\\{").count();
+                self.skip_depth = self.skip_depth.saturating_sub(line.matches("\\}").count());
+                continue;
+            }
+            let Some((name, args)) = parse_request(line) else {
+                self.text(line);
+                continue;
+            };
+            match name {
+                "de" | "de1" | "am" | "ig" => {
+                    for line in lines.by_ref() {
+                        if line.trim_start().starts_with("..") {
+                            break;
+                        }
+                    }
+                }
+                "TS" => {
+                    let table = lines
+                        .by_ref()
+                        .take_while(|line| !line.starts_with(".TE"))
+                        .collect::<Vec<_>>();
+                    self.table(&table);
+                }
+                _ => self.request(name, args),
+            }
+        }
+        self.flush();
+        let mut output: Vec<String> = Vec::new();
+        for line in self.lines {
+            let line = line.trim_end().to_string();
+            // Collapse consecutive blank lines.
+            if line.is_empty() && output.last().map_or(true, |last| last.is_empty()) {
+                continue;
+            }
+            output.push(line);
+        }
+        output.join("\n").trim_end().to_string()
+    }
+
+    /// Handles the request or macro with the given arguments.
+    fn request(&mut self, name: &str, args: &str) {
+        let arguments = split_args(args);
+        let arg = |index: usize| arguments.get(index).map(String::as_str);
+        match name {
+            // Conditionals.
+            "if" | "ie" => {
+                let (condition, body) = parse_condition(args);
+                if name == "ie" {
+                    self.last_condition = condition;
+                }
+                self.conditional(condition, body);
+            }
+            "el" => self.conditional(!self.last_condition, args),
+            "ds" | "ds1" => {
+                if let Some((name, value)) = args.trim_start().split_once(char::is_whitespace) {
+                    let value = value.trim_start();
+                    self.strings.insert(
+                        name.to_string(),
+                        value.strip_prefix('"').unwrap_or(value).to_string(),
+                    );
+                }
+            }
+            // Man macros.
+            "TH" => {
+                let title = format!(
+                    "{}({})",
+                    arg(0).unwrap_or_default(),
+                    arg(1).unwrap_or_default()
+                );
+                self.header(&title, arg(4).unwrap_or_default());
+            }
+            "SH" | "Sh" => {
+                self.synopsis = args.trim() == "SYNOPSIS";
+                self.heading(0, &arguments);
+            }
+            "SS" | "Ss" => self.heading(SUBSECTION_INDENT, &arguments),
+            "PP" | "LP" | "P" | "Pp" | "Lp" | "HP" => {
+                self.flush();
+                self.blank();
+                self.hang = INDENT;
+                self.indent = self.base;
+            }
+            "TP" | "TQ" => {
+                self.flush();
+                if name == "TP" {
+                    self.blank();
+                    self.hang = self.parse_width(arg(0)).unwrap_or(INDENT);
+                }
+                self.indent = self.base.saturating_add(self.hang);
+                self.pending = Some(Pending::Tag);
+            }
+            "IP" => {
+                self.flush();
+                self.blank();
+                self.hang = self.parse_width(arg(1)).unwrap_or(INDENT);
+                self.indent = self.base.saturating_add(self.hang);
+                if let Some(tag) = arg(0).filter(|tag| !tag.is_empty()) {
+                    let tag = self.inline(tag, self.font);
+                    self.tag = Some((self.base, tag));
+                }
+            }
+            "RS" => {
+                self.flush();
+                self.margins.push(self.base);
+                let width = self.parse_width(arg(0)).unwrap_or(self.hang);
+                self.base = self.base.saturating_add(width).min(self.width);
+                self.indent = self.base;
+            }
+            "RE" => {
+                self.flush();
+                self.base = self.margins.pop().unwrap_or(INDENT);
+                self.indent = self.base;
+            }
+            "B" | "SB" => self.font_macro(Font::Bold, &arguments),
+            "I" => self.font_macro(Font::Italic, &arguments),
+            "SM" => self.font_macro(self.font, &arguments),
+            "BR" | "BI" | "IB" | "IR" | "RB" | "RI" => {
+                let fonts = name
+                    .chars()
+                    .map(|c| Font::from_name(&c.to_string()).unwrap_or_default())
+                    .collect::<Vec<_>>();
+                let mut segments = Vec::new();
+                for (i, arg) in arguments.iter().enumerate() {
+                    let mut words = self.inline(arg, fonts[i % 2]);
+                    if i > 0 {
+                        if let Some(first) = words.first_mut() {
+                            first.glue = true;
+                        }
+                    }
+                    segments.extend(words);
+                }
+                self.emit(segments);
+            }
+            "UR" | "MT" => self.link = arg(0).map(String::from),
+            "UE" | "ME" => {
+                if let Some(link) = self.link.take() {
+                    let mut segments =
+                        vec![Segment::new(&format!("<{link}>"), Font::Regular, false)];
+                    if let Some(punctuation) = arg(0) {
+                        segments.push(Segment::new(punctuation, Font::Regular, true));
+                    }
+                    self.emit(segments);
+                }
+            }
+            "OP" => {
+                let mut segments = vec![Segment::new("[", Font::Regular, false)];
+                segments.extend(self.inline(arg(0).unwrap_or_default(), Font::Bold));
+                if let Some(value) = arg(1) {
+                    segments.extend(self.inline(value, Font::Italic));
+                    if let Some(value) = segments.last_mut() {
+                        value.glue = false;
+                    }
+                }
+                segments.push(Segment::new("]", Font::Regular, true));
+                if let Some(flag) = segments.get_mut(1) {
+                    flag.glue = true;
+                }
+                self.emit(segments);
+            }
+            "SY" => {
+                self.flush();
+                let segments = self.inline(args.trim(), Font::Bold);
+                self.emit(segments);
+            }
+            "YS" => self.flush(),
+            "EX" | "nf" => {
+                self.flush();
+                self.fill = false;
+            }
+            "EE" | "fi" => {
+                self.flush();
+                self.fill = true;
+            }
+            "in" => {
+                self.flush();
+                let base = match arg(0) {
+                    Some(width) if width.starts_with('+') => {
+                        let width = self.parse_width(Some(&width[1..])).unwrap_or_default();
+                        self.base.saturating_add(width)
+                    }
+                    Some(width) if width.starts_with('-') => {
+                        let width = self.parse_width(Some(&width[1..])).unwrap_or_default();
+                        self.base.saturating_sub(width)
+                    }
+                    Some(width) => self.parse_width(Some(width)).unwrap_or(self.base),
+                    None => self.prev_base,
+                };
+                self.prev_base = self.base;
+                self.base = base.min(self.width);
+                self.indent = self.base;
+            }
+            "br" | "ti" => self.flush(),
+            "sp" | "ne" => {
+                self.flush();
+                self.lines.push(String::new());
+            }
+            "ft" => self.set_font(Font::from_name(args.trim())),
+            // Mdoc macros.
+            "Dt" => {
+                let title = format!(
+                    "{}({})",
+                    arg(0).unwrap_or_default(),
+                    arg(1).unwrap_or_default()
+                );
+                self.header(&title, "");
+            }
+            "Nm" if self.synopsis && !self.lists.iter().any(|l| l.kind == ListKind::Tag) => {
+                self.flush();
+                self.blank();
+                let segments = self.mdoc(&[name.to_string()].concat_with(&arguments));
+                self.emit(segments);
+            }
+            "Nd" => {
+                let mut segments = vec![Segment::new("\u{2014}", Font::Regular, false)];
+                segments.extend(self.mdoc(&arguments));
+                self.emit(segments);
+            }
+            "Bl" => {
+                self.flush();
+                let kind = match arg(0).unwrap_or_default() {
+                    "-bullet" => ListKind::Bullet,
+                    "-dash" | "-hyphen" => ListKind::Dash,
+                    "-enum" => ListKind::Enum,
+                    "-item" => ListKind::Item,
+                    "-column" => ListKind::Column,
+                    _ => ListKind::Tag,
+                };
+                let option = |name: &str| {
+                    arguments
+                        .iter()
+                        .position(|arg| arg == name)
+                        .and_then(|i| arguments.get(i + 1))
+                };
+                let width = match kind {
+                    // `Ds` is the default indentation of the displays.
+                    ListKind::Tag => option("-width")
+                        .filter(|width| *width != "Ds")
+                        .map(|width| parse_width(width).unwrap_or(width.chars().count() + 2))
+                        .map_or(INDENT + 1, |width| width.min(self.width)),
+                    ListKind::Enum => 4,
+                    _ => 2,
+                };
+                let compact = arguments.iter().any(|arg| arg == "-compact");
+                self.blank_if(!compact);
+                self.lists.push(List {
+                    kind,
+                    width,
+                    compact,
+                    count: 0,
+                    base: self.base,
+                });
+                if option("-offset").is_some() {
+                    self.base += INDENT - 1;
+                }
+                self.indent = self.base;
+            }
+            "It" => self.list_item(&arguments),
+            "El" => {
+                self.flush();
+                if let Some(list) = self.lists.pop() {
+                    self.base = list.base;
+                }
+                self.indent = self.base;
+            }
+            "Bd" => {
+                self.flush();
+                self.blank();
+                self.displays.push(Display {
+                    fill: self.fill,
+                    base: self.base,
+                });
+                if arguments
+                    .iter()
+                    .any(|arg| arg == "-literal" || arg == "-unfilled")
+                {
+                    self.fill = false;
+                }
+                if arguments.iter().any(|arg| arg == "-offset") {
+                    self.base += INDENT - 1;
+                }
+                self.indent = self.base;
+            }
+            "Ed" => {
+                self.flush();
+                if let Some(display) = self.displays.pop() {
+                    self.fill = display.fill;
+                    self.base = display.base;
+                }
+                self.indent = self.base;
+            }
+            "D1" | "Dl" => {
+                self.flush();
+                self.indent = self.base + INDENT - 1;
+                let segments = self.mdoc(&arguments);
+                self.emit(segments);
+                self.flush();
+                self.indent = self.base;
+            }
+            "Ex" => {
+                let name = arguments
+                    .iter()
+                    .find(|arg| !arg.starts_with('-'))
+                    .cloned()
+                    .or_else(|| self.name.clone())
+                    .unwrap_or_default();
+                let mut segments = vec![Segment::new("The", Font::Regular, false)];
+                segments.push(Segment::new(&name, Font::Bold, false));
+                segments.extend(self.words_of(
+                    "utility exits 0 on success, and >0 if an error occurs.",
+                    Font::Regular,
+                ));
+                self.emit(segments);
+            }
+            "Rv" => {
+                let name = arguments
+                    .iter()
+                    .find(|arg| !arg.starts_with('-'))
+                    .cloned()
+                    .unwrap_or_default();
+                let mut segments = vec![Segment::new("The", Font::Regular, false)];
+                segments.push(Segment::new(&format!("{name}()"), Font::Bold, false));
+                segments.extend(self.words_of(
+                    "function returns the value 0 if successful; otherwise the value -1 is \
+                     returned and the global variable errno is set to indicate the error.",
+                    Font::Regular,
+                ));
+                self.emit(segments);
+            }
+            "In" => {
+                let segments = vec![Segment::new(
+                    &format!("#include <{}>", arg(0).unwrap_or_default()),
+                    Font::Bold,
+                    false,
+                )];
+                self.flush();
+                self.emit(segments);
+                self.flush();
+            }
+            "Fo" => {
+                let segments = vec![
+                    Segment::new(arg(0).unwrap_or_default(), Font::Bold, false),
+                    Segment::new("(", Font::Regular, true),
+                ];
+                self.emit(segments);
+            }
+            "Fc" => self.emit(vec![Segment::new(")", Font::Regular, true)]),
+            "%A" | "%B" | "%D" | "%J" | "%N" | "%O" | "%P" | "%R" | "%T" | "%U" | "%V" => {
+                let segments = self.mdoc(&arguments);
+                self.emit(segments);
+            }
+            _ if MDOC_CALLABLE.contains(&name) => {
+                if name == "Nm" && self.name.is_none() {
+                    self.name = arg(0).map(String::from);
+                }
+                let mut segments = self.mdoc(&[name.to_string()].concat_with(&arguments));
+                if !self.spacing {
+                    let skip = usize::from(self.spacing_off);
+                    segments
+                        .iter_mut()
+                        .skip(skip)
+                        .for_each(|segment| segment.glue = true);
+                    self.spacing_off = false;
+                }
+                self.emit(segments);
+            }
+            "Sm" => {
+                self.spacing = arg(0).map_or(!self.spacing, |mode| mode != "off");
+                self.spacing_off = !self.spacing;
+            }
+            // Other requests (e.g. page layout) are ignored.
+            _ => {}
+        }
+    }
+
+    /// Handles the body of a conditional request.
+    fn conditional(&mut self, condition: bool, body: &str) {
+        let body = body.trim_start();
+        let (body, block) = match body.strip_prefix("\\{") {
+            Some(body) => (body.trim_start_matches("\\").trim_start(), true),
+            None => (body, false),
+        };
+        if !condition {
+            if block && !body.contains("\\}") {
+                self.skip_depth = 1;
+            }
+            return;
+        }
+        let body = body.replace("\\}", "");
+        if body.trim().is_empty() {
+            return;
+        }
+        match parse_request(&body) {
+            Some((name, args)) => self.request(name, args),
+            None => self.text(&body),
+        }
+    }
+
+    /// Handles a line of text.
+    fn text(&mut self, line: &str) {
+        let line = line.replace("\\}", "");
+        if !self.fill {
+            self.flush();
+            let segments = self.inline_preserving(&line);
+            let line = self.render_segments(&segments, self.indent);
+            self.lines.push(line);
+            return;
+        }
+        if line.trim().is_empty() {
+            self.flush();
+            self.blank();
+            return;
+        }
+        if line.starts_with(char::is_whitespace) {
+            self.flush();
+        }
+        let font = self.next_font.take();
+        let segments = match font {
+            Some(font) => self.inline(&line, font),
+            None => {
+                let font = self.font;
+                self.inline(&line, font)
+            }
+        };
+        self.emit(segments);
+    }
+
+    /// Adds the segments to the current paragraph or uses them for the pending element.
+    fn emit(&mut self, segments: Vec<Segment>) {
+        match self.pending.take() {
+            Some(Pending::Tag) => {
+                if let Some((indent, tag)) = self.tag.take() {
+                    // Multiple tags (`.TQ`).
+                    let line = self.render_segments(&tag, indent);
+                    self.lines.push(line);
+                }
+                self.tag = Some((self.base, segments));
+            }
+            Some(Pending::Heading(indent)) => self.heading_line(indent, &segments),
+            None if !self.fill => {
+                let line = self.render_segments(&segments, self.indent);
+                self.lines.push(line);
+            }
+            None => self.words.extend(segments),
+        }
+    }
+
+    /// Handles a font macro (e.g. `.B`).
+    fn font_macro(&mut self, font: Font, arguments: &[String]) {
+        if arguments.is_empty() {
+            self.next_font = Some(font);
+        } else {
+            let segments = self.inline(&arguments.join(" "), font);
+            self.emit(segments);
+        }
+    }
+
+    /// Sets the current font (`None` for the previous font).
+    fn set_font(&mut self, font: Option<Font>) {
+        let font = font.unwrap_or(self.prev_font);
+        self.prev_font = self.font;
+        self.font = font;
+    }
+
+    /// Adds the page header.
+    fn header(&mut self, title: &str, manual: &str) {
+        let title_width = title.chars().count();
+        let manual_width = manual.chars().count();
+        // The manual name is centered.
+        let left = (self.width.saturating_sub(manual_width) / 2)
+            .saturating_sub(title_width)
+            .max(1);
+        let right = self
+            .width
+            .saturating_sub(title_width * 2 + manual_width + left)
+            .max(1);
+        self.lines.push(format!(
+            "{title}{}{manual}{}{title}",
+            " ".repeat(left),
+            " ".repeat(right)
+        ));
+        self.lines.push(String::new());
+    }
+
+    /// Adds a section heading.
+    fn heading(&mut self, indent: usize, arguments: &[String]) {
+        self.flush();
+        self.margins.clear();
+        self.base = INDENT;
+        self.hang = INDENT;
+        self.indent = self.base;
+        self.fill = true;
+        if arguments.is_empty() {
+            self.pending = Some(Pending::Heading(indent));
+        } else {
+            let segments = self.inline(&arguments.join(" "), Font::Bold);
+            self.heading_line(indent, &segments);
+        }
+    }
+
+    /// Adds the heading line.
+    fn heading_line(&mut self, indent: usize, segments: &[Segment]) {
+        self.blank();
+        let segments = segments
+            .iter()
+            .map(|segment| Segment {
+                font: Font::Bold,
+                ..segment.clone()
+            })
+            .collect::<Vec<_>>();
+        let line = self.render_segments(&segments, indent);
+        self.lines.push(line);
+        self.section_start = self.lines.len();
+    }
+
+    /// Handles an item of the current `mdoc` list.
+    fn list_item(&mut self, arguments: &[String]) {
+        self.flush();
+        let Some(list) = self.lists.last_mut() else {
+            return;
+        };
+        list.count += 1;
+        let (kind, width, compact, count) = (list.kind, list.width, list.compact, list.count);
+        if !compact {
+            self.blank();
+        }
+        self.indent = self.base.saturating_add(width);
+        let tag = match kind {
+            ListKind::Tag => self.mdoc(arguments),
+            ListKind::Bullet => vec![Segment::new("\u{2022}", Font::Regular, false)],
+            ListKind::Dash => vec![Segment::new("-", Font::Regular, false)],
+            ListKind::Enum => vec![Segment::new(&format!("{count}."), Font::Regular, false)],
+            ListKind::Item => Vec::new(),
+            ListKind::Column => {
+                self.indent = self.base;
+                let columns = arguments
+                    .split(|arg| arg == "Ta")
+                    .map(|column| {
+                        let segments = self.mdoc(column);
+                        self.render_segments(&segments, 0)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                self.words
+                    .push(Segment::new(&columns, Font::Regular, false));
+                return;
+            }
+        };
+        if !tag.is_empty() {
+            self.tag = Some((self.base, tag));
+        }
+    }
+
+    /// Renders a table (`.TS`/`.TE`) as lines with columns separated by spaces.
+    fn table(&mut self, lines: &[&str]) {
+        self.flush();
+        let mut separator = '\t';
+        let mut rows = lines.iter().peekable();
+        // Options (e.g. `tab(:);`).
+        if let Some(options) = rows.peek().filter(|line| line.trim_end().ends_with(';')) {
+            if let Some(tab) = options.split("tab(").nth(1).and_then(|v| v.chars().next()) {
+                separator = tab;
+            }
+            rows.next();
+        }
+        // Format lines (e.g. `l l.`).
+        for line in rows.by_ref() {
+            if line.trim_end().ends_with('.') {
+                break;
+            }
+        }
+        for row in rows {
+            let row = row.trim_start_matches("T{").replace("T}", "");
+            if row.starts_with('.') || matches!(row.trim(), "_" | "=" | "") {
+                continue;
+            }
+            let segments = row
+                .split(separator)
+                .map(|column| {
+                    let segments = self.inline_preserving(column.trim());
+                    self.render_segments(&segments, 0)
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            self.lines
+                .push(format!("{}{segments}", " ".repeat(self.indent)));
+        }
+    }
+
+    /// Adds a blank line if the last line is not blank.
+    fn blank(&mut self) {
+        self.blank_if(true);
+    }
+
+    /// Adds a blank line if the given condition holds and the last line is not blank or a heading.
+    fn blank_if(&mut self, condition: bool) {
+        if condition
+            && self.lines.len() != self.section_start
+            && self
+                .lines
+                .last()
+                .is_some_and(|line| !line.trim().is_empty())
+        {
+            self.lines.push(String::new());
+        }
+    }
+
+    /// Parses the width of an indentation, limited to the width of the page.
+    fn parse_width(&self, width: Option<&str>) -> Option<usize> {
+        width
+            .and_then(parse_width)
+            .map(|width| width.min(self.width))
+    }
+
+    /// Fills the words of the current paragraph into lines.
+    fn flush(&mut self) {
+        let tag = self.tag.take();
+        if self.words.is_empty() && tag.is_none() {
+            return;
+        }
+        let mut words: Vec<Vec<Segment>> = Vec::new();
+        for segment in self.words.drain(..) {
+            match words.last_mut() {
+                Some(word) if segment.glue => word.push(segment),
+                _ => words.push(vec![segment]),
+            }
+        }
+        let mut line = " ".repeat(self.indent);
+        let mut column = self.indent;
+        let mut empty = true;
+        if let Some((indent, tag)) = tag {
+            let tag_width = visible_width(&tag);
+            if !words.is_empty() && indent + tag_width < self.indent {
+                line = " ".repeat(indent);
+                line.push_str(&self.render_segments(&tag, 0));
+                line.push_str(&" ".repeat(self.indent - indent - tag_width));
+            } else {
+                let tag = self.render_segments(&tag, indent);
+                self.lines.push(tag);
+            }
+        }
+        for word in words {
+            let width = visible_width(&word);
+            if !empty && column + 1 + width > self.width {
+                self.lines.push(line);
+                line = " ".repeat(self.indent);
+                column = self.indent;
+                empty = true;
+            }
+            if !empty {
+                line.push(' ');
+                column += 1;
+            }
+            line.push_str(&self.render_segments(&word, 0));
+            column += width;
+            empty = false;
+        }
+        if !empty {
+            self.lines.push(line);
+        }
+    }
+
+    /// Renders the segments with the given indentation.
+    fn render_segments(&self, segments: &[Segment], indent: usize) -> String {
+        let mut line = " ".repeat(indent);
+        for (i, segment) in segments.iter().enumerate() {
+            if i > 0 && !segment.glue {
+                line.push(' ');
+            }
+            let text = segment.text.as_str();
+            let styled = match segment.font {
+                _ if !self.styled || text.trim().is_empty() => text.to_string(),
+                Font::Regular => text.to_string(),
+                Font::Bold => text.bold().to_string(),
+                Font::Italic => text.underline().to_string(),
+            };
+            line.push_str(&styled);
+        }
+        line
+    }
+
+    /// Splits the plain text into words with the given font.
+    fn words_of(&self, text: &str, font: Font) -> Vec<Segment> {
+        text.split_whitespace()
+            .map(|word| Segment::new(word, font, false))
+            .collect()
+    }
+
+    /// Parses the text with the given font, keeping the font changes for the following lines.
+    fn inline(&mut self, text: &str, font: Font) -> Vec<Segment> {
+        let current = self.font;
+        self.font = font;
+        let segments = self.parse_inline(text, false);
+        if font == current {
+            return segments;
+        }
+        self.font = current;
+        segments
+    }
+
+    /// Parses the text with the current font, preserving the whitespace (e.g. in no-fill mode).
+    fn inline_preserving(&mut self, text: &str) -> Vec<Segment> {
+        let mut segments = self.parse_inline(&expand_tabs(text), true);
+        for segment in segments.iter_mut() {
+            segment.glue = true;
+        }
+        segments
+    }
+
+    /// Parses the escape sequences and words in the text.
+    fn parse_inline(&mut self, text: &str, preserve: bool) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        let mut current = String::new();
+        let mut glue = false;
+        let mut chars = text.chars().peekable();
+        macro_rules! push {
+            () => {
+                if !current.is_empty() {
+                    segments.push(Segment::new(&current, self.font, glue));
+                    current.clear();
+                    glue = true;
+                }
+            };
+        }
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    let Some(escape) = chars.next() else {
+                        break;
+                    };
+                    match escape {
+                        'f' => {
+                            let name = read_name(&mut chars);
+                            push!();
+                            self.set_font(Font::from_name(&name));
+                        }
+                        '(' | '[' | 'C' => {
+                            let name = match escape {
+                                '(' => chars.by_ref().take(2).collect(),
+                                '[' => read_until(&mut chars, ']'),
+                                _ => read_quoted(&mut chars),
+                            };
+                            current.push_str(glyph(&name));
+                        }
+                        '*' => {
+                            let name = read_name(&mut chars);
+                            let value = self
+                                .strings
+                                .get(&name)
+                                .cloned()
+                                .unwrap_or_else(|| predefined_string(&name).to_string());
+                            push!();
+                            let was_glued = glue;
+                            let mut expanded = self.parse_inline(&value, preserve);
+                            if let Some(first) = expanded.first_mut() {
+                                first.glue = was_glued;
+                            }
+                            if !expanded.is_empty() {
+                                glue = true;
+                            }
+                            segments.extend(expanded);
+                        }
+                        'e' | '\\' => current.push('\\'),
+                        '-' => current.push('-'),
+                        ' ' | '~' | '0' => current.push(' '),
+                        '\'' => current.push('\''),
+                        '`' => current.push('`'),
+                        '.' => current.push('.'),
+                        't' => current.push(' '),
+                        'n' | 'g' | 'k' | 'F' | 'm' | 'M' | 'V' | 'Y' => {
+                            read_name(&mut chars);
+                        }
+                        's' => {
+                            while chars
+                                .peek()
+                                .is_some_and(|c| c.is_ascii_digit() || "+-".contains(*c))
+                            {
+                                chars.next();
+                            }
+                        }
+                        'h' | 'v' | 'w' | 'o' | 'l' | 'L' | 'D' | 'X' | 'Z' | 'b' | 'x' | 'N'
+                        | 'R' | 'A' | 'B' => {
+                            read_quoted(&mut chars);
+                        }
+                        '"' | '#' => break,
+                        _ => {}
+                    }
+                }
+                c if c.is_whitespace() && !preserve => {
+                    if !current.is_empty() {
+                        segments.push(Segment::new(&current, self.font, glue));
+                        current.clear();
+                    }
+                    glue = false;
+                }
+                c => current.push(c),
+            }
+        }
+        if !current.is_empty() {
+            segments.push(Segment::new(&current, self.font, glue));
+        }
+        segments
+    }
+
+    /// Parses the arguments of an `mdoc` macro line (e.g. `Op Fl a Ar file`).
+    fn mdoc(&mut self, tokens: &[String]) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        let mut font = Font::Regular;
+        let mut macro_name: Option<&str> = None;
+        let mut glue_next = false;
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens[i].as_str();
+            i += 1;
+            if MDOC_CALLABLE.contains(&token) {
+                macro_name = Some(token);
+                font = match token {
+                    "Fl" | "Cm" | "Ic" | "Nm" | "Sy" | "Fn" | "Cd" | "Ms" | "Ft" => Font::Bold,
+                    "Ar" | "Pa" | "Va" | "Em" | "Fa" | "Vt" | "Ad" | "Er" => Font::Italic,
+                    _ => Font::Regular,
+                };
+                let enclosure = match token {
+                    "Op" => Some(("[", "]")),
+                    "Dq" => Some(("\u{201c}", "\u{201d}")),
+                    "Qq" => Some(("\"", "\"")),
+                    "Sq" | "Ql" => Some(("\u{2018}", "\u{2019}")),
+                    "Pq" => Some(("(", ")")),
+                    "Aq" => Some(("<", ">")),
+                    "Bq" => Some(("[", "]")),
+                    "Brq" => Some(("{", "}")),
+                    _ => None,
+                };
+                if let Some((open, close)) = enclosure {
+                    let rest = &tokens[i..];
+                    let trailing = rest
+                        .iter()
+                        .rev()
+                        .take_while(|token| is_closing_delimiter(token))
+                        .count();
+                    let inner = self.mdoc(&rest[..rest.len() - trailing]);
+                    segments.push(Segment::new(open, Font::Regular, glue_next));
+                    segments.extend(inner.into_iter().enumerate().map(|(j, segment)| Segment {
+                        glue: j == 0 || segment.glue,
+                        ..segment
+                    }));
+                    segments.push(Segment::new(close, Font::Regular, true));
+                    for token in &rest[rest.len() - trailing..] {
+                        segments.push(Segment::new(token, Font::Regular, true));
+                    }
+                    return segments;
+                }
+                let has_args = tokens.get(i).is_some_and(|next| {
+                    !MDOC_CALLABLE.contains(&next.as_str()) && !is_closing_delimiter(next)
+                });
+                match token {
+                    "Ns" => glue_next = true,
+                    "Oo" => {
+                        segments.push(Segment::new("[", Font::Regular, glue_next));
+                        glue_next = true;
+                    }
+                    "Oc" => segments.push(Segment::new("]", Font::Regular, true)),
+                    "Fl" if !has_args => {
+                        segments.push(Segment::new("-", Font::Bold, glue_next));
+                        glue_next = false;
+                    }
+                    "Ar" if !has_args => {
+                        segments.push(Segment::new("file", Font::Italic, glue_next));
+                        segments.push(Segment::new("...", Font::Italic, false));
+                        glue_next = false;
+                    }
+                    "Nm" if !has_args => {
+                        let name = self.name.clone().unwrap_or_default();
+                        segments.push(Segment::new(&name, Font::Bold, glue_next));
+                        glue_next = false;
+                    }
+                    "Ux" => segments.push(Segment::new("UNIX", Font::Regular, glue_next)),
+                    "Bx" => segments.push(Segment::new("BSD", Font::Regular, glue_next)),
+                    "At" => segments.push(Segment::new("AT&T UNIX", Font::Regular, glue_next)),
+                    "Xr" => {
+                        let name = tokens.get(i).cloned().unwrap_or_default();
+                        let section = tokens
+                            .get(i + 1)
+                            .filter(|section| !is_closing_delimiter(section))
+                            .cloned();
+                        i += 1 + usize::from(section.is_some());
+                        let mut xr = self.inline(&name, Font::Bold);
+                        if let Some(first) = xr.first_mut() {
+                            first.glue = glue_next;
+                        }
+                        segments.extend(xr);
+                        if let Some(section) = section {
+                            segments.push(Segment::new(
+                                &format!("({section})"),
+                                Font::Regular,
+                                true,
+                            ));
+                        }
+                        glue_next = false;
+                    }
+                    "Fn" => {
+                        let name = tokens.get(i).cloned().unwrap_or_default();
+                        let arguments = tokens[(i + 1).min(tokens.len())..]
+                            .iter()
+                            .take_while(|token| !is_closing_delimiter(token))
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        i += 1 + arguments.len();
+                        segments.push(Segment::new(&name, Font::Bold, glue_next));
+                        segments.push(Segment::new(
+                            &format!("({})", arguments.join(", ")),
+                            Font::Regular,
+                            true,
+                        ));
+                        glue_next = false;
+                    }
+                    "Lk" => {
+                        let url = tokens.get(i).cloned().unwrap_or_default();
+                        let text = tokens
+                            .get(i + 1)
+                            .filter(|t| !is_closing_delimiter(t))
+                            .cloned();
+                        i += 1 + usize::from(text.is_some());
+                        if let Some(text) = text {
+                            segments.extend(self.inline(&text, Font::Regular));
+                        }
+                        segments.push(Segment::new(&format!("<{url}>"), Font::Regular, false));
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            if is_closing_delimiter(token) {
+                segments.push(Segment::new(token, Font::Regular, true));
+                continue;
+            }
+            if matches!(token, "(" | "[") {
+                segments.push(Segment::new(token, Font::Regular, glue_next));
+                glue_next = true;
+                continue;
+            }
+            let text = if macro_name == Some("Fl") {
+                format!("-{token}")
+            } else {
+                token.to_string()
+            };
+            if macro_name == Some("Nm") && self.name.is_none() {
+                self.name = Some(text.to_string());
+            }
+            let mut words = self.inline(&text, font);
+            if let Some(first) = words.first_mut() {
+                first.glue = glue_next;
+            }
+            glue_next = false;
+            segments.extend(words);
+        }
+        segments
+    }
+}
+
+/// Helper for prepending an element to a list of arguments.
+trait ConcatWith {
+    /// Returns the concatenation of the lists.
+    fn concat_with(self, other: &[String]) -> Vec<String>;
+}
+
+impl ConcatWith for [String; 1] {
+    fn concat_with(self, other: &[String]) -> Vec<String> {
+        let mut list = self.to_vec();
+        list.extend_from_slice(other);
+        list
+    }
+}
+
+/// Returns `true` if the token is a closing delimiter in `mdoc` (e.g. `.` or `)`).
+fn is_closing_delimiter(token: &str) -> bool {
+    matches!(token, "." | "," | ":" | ";" | ")" | "]" | "?" | "!")
+}
+
+/// Parses the request line into the name and the arguments.
+///
+/// Returns `None` if the line is not a request (i.e. text).
+fn parse_request(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('.').or_else(|| line.strip_prefix('\''))?;
+    let rest = rest.trim_start();
+    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    let (name, args) = rest.split_at(end);
+    Some((name, args.trim_start()))
+}
+
+/// Parses the condition of `.if`/`.ie` and returns its result and the body.
+///
+/// Only the conditions that are commonly used in the manual pages are evaluated
+/// (`n` is true for terminal output, `t` is false, `\n(.g` is true for groff compatibility).
+fn parse_condition(args: &str) -> (bool, &str) {
+    let args = args.trim_start();
+    let (negated, args) = match args.strip_prefix('!') {
+        Some(args) => (true, args),
+        None => (false, args),
+    };
+    let end = args.find(char::is_whitespace).unwrap_or(args.len());
+    let (condition, body) = args.split_at(end);
+    let result = matches!(condition, "n" | "o" | "\\n(.g" | "\\n[.g]");
+    (result != negated, body)
+}
+
+/// Splits the macro arguments, respecting the quotes.
+fn split_args(args: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut chars = args.chars().peekable();
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        let Some(&first) = chars.peek() else {
+            break;
+        };
+        let mut argument = String::new();
+        if first == '"' {
+            chars.next();
+            while let Some(c) = chars.next() {
+                if c == '"' {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        argument.push('"');
+                        continue;
+                    }
+                    break;
+                }
+                argument.push(c);
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                chars.next();
+                argument.push(c);
+                if c == '\\' {
+                    if let Some(escaped) = chars.next() {
+                        argument.push(escaped);
+                    }
+                }
+            }
+        }
+        if argument.starts_with("\\\"") {
+            break;
+        }
+        arguments.push(argument);
+    }
+    arguments
+}
+
+/// Parses the width of an indentation (e.g. `4n`, `0.5i`).
+fn parse_width(width: &str) -> Option<usize> {
+    let number = width.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let value = number.parse::<f64>().ok()?;
+    let value = match width.chars().last() {
+        Some('i') => value * 10.0,
+        Some('c') => value * 4.0,
+        Some('m' | 'n') | None => value,
+        _ => value,
+    };
+    Some(value.round().max(0.0) as usize)
+}
+
+/// Reads the name of an escape sequence (e.g. `B`, `(CW` or `[BI]`).
+fn read_name<I: Iterator<Item = char>>(chars: &mut std::iter::Peekable<I>) -> String {
+    match chars.next() {
+        Some('(') => chars.take(2).collect(),
+        Some('[') => read_until(chars, ']'),
+        Some(c) => c.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Reads the characters until the given delimiter.
+fn read_until<I: Iterator<Item = char>>(chars: &mut I, delimiter: char) -> String {
+    chars.take_while(|c| *c != delimiter).collect()
+}
+
+/// Reads a quoted argument of an escape sequence (e.g. `'...'`).
+fn read_quoted<I: Iterator<Item = char>>(chars: &mut std::iter::Peekable<I>) -> String {
+    match chars.next() {
+        Some(quote) => read_until(chars, quote),
+        None => String::new(),
+    }
+}
+
+/// Expands the tabs to the tab stops of 8 columns.
+fn expand_tabs(text: &str) -> String {
+    let mut expanded = String::new();
+    let mut column = 0;
+    for c in text.chars() {
+        if c == '\t' {
+            let spaces = 8 - column % 8;
+            expanded.push_str(&" ".repeat(spaces));
+            column += spaces;
+        } else {
+            expanded.push(c);
+            column += 1;
+        }
+    }
+    expanded
+}
+
+/// Returns the visible width of the segments.
+fn visible_width(segments: &[Segment]) -> usize {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| segment.text.chars().count() + usize::from(i > 0 && !segment.glue))
+        .sum()
+}
+
+/// Returns the predefined string with the given name (e.g. `\*(lq`).
+fn predefined_string(name: &str) -> &'static str {
+    match name {
+        "R" => "\u{ae}",
+        "Tm" => "\u{2122}",
+        "lq" => "\u{201c}",
+        "rq" => "\u{201d}",
+        _ => "",
+    }
+}
+
+/// Returns the glyph with the given name (e.g. `\(em`).
+fn glyph(name: &str) -> &'static str {
+    match name {
+        "em" => "\u{2014}",
+        "en" => "\u{2013}",
+        "bu" => "\u{2022}",
+        "aq" => "'",
+        "dq" => "\"",
+        "lq" => "\u{201c}",
+        "rq" => "\u{201d}",
+        "oq" => "\u{2018}",
+        "cq" => "\u{2019}",
+        "co" => "\u{a9}",
+        "rg" => "\u{ae}",
+        "tm" => "\u{2122}",
+        "hy" | "mi" | "-" => "-",
+        "ti" | "a~" => "~",
+        "ha" | "a^" => "^",
+        "rs" => "\\",
+        "sl" => "/",
+        "pl" => "+",
+        "eq" => "=",
+        "mu" => "\u{d7}",
+        "di" => "\u{f7}",
+        "<=" => "\u{2264}",
+        ">=" => "\u{2265}",
+        "!=" => "\u{2260}",
+        "->" | "ra" => "\u{2192}",
+        "<-" | "la" => "\u{2190}",
+        "de" => "\u{b0}",
+        "Fo" => "\u{ab}",
+        "Fc" => "\u{bb}",
+        "ga" => "`",
+        "aa" => "\u{b4}",
+        "ba" | "or" | "br" => "|",
+        "lB" => "[",
+        "rB" => "]",
+        "lC" => "{",
+        "rC" => "}",
+        "at" => "@",
+        "sh" => "#",
+        "Do" => "$",
+        "eu" | "Eu" => "\u{20ac}",
+        "Po" => "\u{a3}",
+        "ss" => "\u{df}",
+        "ct" => "\u{a2}",
+        "sc" => "\u{a7}",
+        "ps" => "\u{b6}",
+        "dg" => "\u{2020}",
+        "12" => "\u{bd}",
+        _ => "",
+    }
+}
+
+/// Renders the roff source of a manual page (`man` or `mdoc` macros) as terminal text.
+///
+/// If `styled` is `false`, the text is not styled with escape sequences (e.g. for parsing).
+pub fn render_roff(source: &str, styled: bool, width: usize) -> String {
+    Renderer::new(styled, width).render(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_render_man() {
+        let source = r#".\" comment
+.TH LS "1" "September 2022" "GNU coreutils 9.1" "User Commands"
+.SH NAME
+ls \- list directory contents
+.SH SYNOPSIS
+.B ls
+[\fI\,OPTION\/\fR]... [\fI\,FILE\/\fR]...
+.SH DESCRIPTION
+.PP
+List information about the FILEs.
+.TP
+\fB\-a\fR, \fB\-\-all\fR
+do not ignore entries starting with .
+.TP
+.B \-\-author
+print the author
+.IP \(bu 2
+item
+.RS
+.nf
+ls \-la
+.fi
+.RE
+.SS "Exit status"
+See
+.BR dir (1).
+"#;
+        assert_eq!(
+            r#"LS(1)                            User Commands                             LS(1)
+
+NAME
+       ls - list directory contents
+
+SYNOPSIS
+       ls [OPTION]... [FILE]...
+
+DESCRIPTION
+       List information about the FILEs.
+
+       -a, --all
+              do not ignore entries starting with .
+
+       --author
+              print the author
+
+       • item
+         ls -la
+
+   Exit status
+       See dir(1)."#,
+            render_roff(source, false, DEFAULT_WIDTH)
+        );
+    }
+
+    #[test]
+    fn test_render_mdoc() {
+        let source = r#".Dd January 1, 2024
+.Dt TOUCH 1
+.Os
+.Sh NAME
+.Nm touch
+.Nd change file access and modification times
+.Sh SYNOPSIS
+.Nm
+.Op Fl acm
+.Op Fl r Ar file
+.Ar
+.Sh DESCRIPTION
+The
+.Nm
+utility sets the modification time, see
+.Xr stat 2 .
+.Bl -tag -width Ds
+.It Fl a
+Change the access time.
+.El
+.Sh EXIT STATUS
+.Ex -std
+"#;
+        assert_eq!(
+            "TOUCH(1)                                                                TOUCH(1)
+
+NAME
+       touch \u{2014} change file access and modification times
+
+SYNOPSIS
+       touch [-acm] [-r file] file ...
+
+DESCRIPTION
+       The touch utility sets the modification time, see stat(2).
+
+       -a      Change the access time.
+
+EXIT STATUS
+       The touch utility exits 0 on success, and >0 if an error occurs.",
+            render_roff(source, false, DEFAULT_WIDTH)
+        );
+    }
+
+    #[test]
+    fn test_render_styled() {
+        assert_eq!(
+            format!("       {} {}", "ls".bold(), "FILE".underline()),
+            render_roff(".SH NAME\n.B ls\n.I FILE", true, DEFAULT_WIDTH)
+                .lines()
+                .last()
+                .unwrap_or_default()
+        );
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            "       one two\n       three",
+            render_roff("one two three", false, 15)
+        );
+        assert_eq!(
+            "           a\n               b\n               c\n           d",
+            render_roff(
+                ".in +4n\na\n.RS 1e30\nb\n.RE\n.in +4n\nc\n.in\nd",
+                false,
+                15
+            )
+        );
+        assert_eq!(
+            vec![String::from("a b"), String::from("c"), String::from("d")],
+            split_args(r#"  "a b" c d \" comment"#)
+        );
+    }
+}
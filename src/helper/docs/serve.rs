@@ -0,0 +1,315 @@
+use crate::error::{Error, Result};
+use crate::helper::args::FOUND_EMOTICON;
+use colored::*;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Default address of the mirror server.
+pub const DEFAULT_SERVE_ADDRESS: &str = "127.0.0.1:8080";
+
+/// Timeout of reading the request and writing the response.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum length of a line of the request.
+const MAX_LINE_LENGTH: u64 = 8192;
+
+/// Maximum number of the headers of the request.
+const MAX_HEADERS: usize = 100;
+
+/// Maximum number of the connections that are handled at the same time.
+const MAX_CONNECTIONS: usize = 64;
+
+/// HTTP server for the local provider mirror.
+///
+/// Files are served with the same URL layout as the mirror directory
+/// (e.g. `<dir>/eg/tar.md` as `http://<address>/eg/tar.md`), so the providers can be pointed
+/// at the server with e.g. `eg_url = "http://<address>/eg"`.
+#[derive(Debug)]
+pub struct MirrorServer {
+    /// Listener of the server.
+    listener: TcpListener,
+    /// Directory to serve.
+    dir: PathBuf,
+}
+
+/// A parsed HTTP request.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Request {
+    /// Request method (e.g. `GET`).
+    method: String,
+    /// Decoded request path.
+    path: String,
+    /// `If-None-Match` header.
+    if_none_match: Option<String>,
+}
+
+impl MirrorServer {
+    /// Binds the server to the given address.
+    pub fn bind(dir: PathBuf, address: &str) -> Result<Self> {
+        if !dir.is_dir() {
+            return Err(Error::ProviderError(format!(
+                "Mirror directory {dir:?} does not exist"
+            )));
+        }
+        Ok(Self {
+            listener: TcpListener::bind(address)?,
+            dir,
+        })
+    }
+
+    /// Returns the local address of the server.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Serves the requests until the process is terminated.
+    ///
+    /// Each connection is handled in a separate thread. If [`MAX_CONNECTIONS`] connections are
+    /// already being handled, the new ones are rejected with `503 Service Unavailable`.
+    pub fn run(self) -> Result<()> {
+        let connections = Arc::new(AtomicUsize::new(0));
+        for stream in self.listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let _ = stream.set_read_timeout(Some(CONNECTION_TIMEOUT));
+            let _ = stream.set_write_timeout(Some(CONNECTION_TIMEOUT));
+            if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                connections.fetch_sub(1, Ordering::SeqCst);
+                let _ = stream.write_all(&build_status(503, "Service Unavailable"));
+                continue;
+            }
+            let guard = ConnectionGuard(Arc::clone(&connections));
+            let dir = self.dir.to_path_buf();
+            thread::spawn(move || {
+                handle_connection(stream, &dir);
+                drop(guard);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Decrements the number of the active connections when dropped.
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Reads a line of the request that is at most [`MAX_LINE_LENGTH`] bytes long.
+fn read_line<R: BufRead>(reader: &mut R, line: &mut String) -> Result<usize> {
+    line.clear();
+    let length = reader.by_ref().take(MAX_LINE_LENGTH).read_line(line)?;
+    if length as u64 == MAX_LINE_LENGTH && !line.ends_with('\n') {
+        return Err(Error::ProviderError(String::from(
+            "Request line is too long",
+        )));
+    }
+    Ok(length)
+}
+
+/// Reads the request from the stream.
+fn read_request<R: BufRead>(reader: &mut R) -> Result<Request> {
+    let mut line = String::new();
+    read_line(reader, &mut line)?;
+    let mut parts = line.split_whitespace();
+    let mut request = Request {
+        method: parts.next().unwrap_or_default().to_string(),
+        path: percent_decode(
+            parts
+                .next()
+                .unwrap_or_default()
+                .split(['?', '#'])
+                .next()
+                .unwrap_or_default(),
+        ),
+        ..Default::default()
+    };
+    for _ in 0..=MAX_HEADERS {
+        if read_line(reader, &mut line)? == 0 || line.trim().is_empty() {
+            return Ok(request);
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("if-none-match") {
+                request.if_none_match = Some(value.trim().to_string());
+            }
+        }
+    }
+    Err(Error::ProviderError(String::from(
+        "Too many request headers",
+    )))
+}
+
+/// Decodes the percent-encoded string.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Returns the file for the request path if it exists in the directory.
+fn resolve_path(dir: &Path, path: &str) -> Option<PathBuf> {
+    let path = Path::new(path.trim_start_matches('/'));
+    if path
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    Some(dir.join(path)).filter(|path| path.is_file())
+}
+
+/// Handles the connection and writes the response.
+fn handle_connection(mut stream: TcpStream, dir: &Path) {
+    let response = match read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => build_response(&request, dir),
+        Err(_) => build_status(400, "Bad Request"),
+    };
+    let _ = stream.write_all(&response);
+}
+
+/// Builds the response for the request.
+fn build_response(request: &Request, dir: &Path) -> Vec<u8> {
+    if request.method != "GET" && request.method != "HEAD" {
+        return build_status(405, "Method Not Allowed");
+    }
+    let Some(path) = resolve_path(dir, &request.path) else {
+        return build_status(404, "Not Found");
+    };
+    let (Ok(metadata), Ok(contents)) = (fs::metadata(&path), fs::read(&path)) else {
+        return build_status(404, "Not Found");
+    };
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .unwrap_or_default();
+    let etag = format!("\"{}-{}\"", modified.as_secs(), contents.len());
+    if request.if_none_match.as_deref() == Some(etag.as_str()) {
+        return format!("HTTP/1.1 304 Not Modified\r\nETag: {etag}\r\nConnection: close\r\n\r\n")
+            .into_bytes();
+    }
+    let mut response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\n\
+         ETag: {etag}\r\nConnection: close\r\n\r\n",
+        contents.len()
+    )
+    .into_bytes();
+    if request.method == "GET" {
+        response.extend(contents);
+    }
+    response
+}
+
+/// Builds a response with the given status and no contents.
+fn build_status(code: u16, reason: &str) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 {code} {reason}\r\nContent-Type: text/plain; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{reason}",
+        reason.len()
+    )
+    .into_bytes()
+}
+
+/// Serves the local mirror over HTTP.
+pub fn serve_mirror<Output: Write>(dir: PathBuf, address: &str, output: &mut Output) -> Result<()> {
+    let server = MirrorServer::bind(dir.to_path_buf(), address)?;
+    writeln!(
+        output,
+        "{} {} {} on {}",
+        FOUND_EMOTICON.magenta(),
+        "serving".green().bold(),
+        format!("{:?}", dir).white().italic(),
+        format!("http://{}", server.local_addr()?).cyan().bold()
+    )?;
+    output.flush()?;
+    server.run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::docs::cache::PageCache;
+    use crate::helper::docs::{eg, HelpProvider};
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_read_request() -> Result<()> {
+        let mut request = "GET /cheat.sh/git%2Fcommit?T HTTP/1.1\r\n\
+                           Host: localhost\r\n\
+                           If-None-Match: \"1-2\"\r\n\r\n"
+            .as_bytes();
+        let request = read_request(&mut request)?;
+        assert_eq!(
+            Request {
+                method: String::from("GET"),
+                path: String::from("/cheat.sh/git/commit"),
+                if_none_match: Some(String::from("\"1-2\"")),
+            },
+            request
+        );
+        let line = format!(
+            "GET /{} HTTP/1.1\r\n\r\n",
+            "a".repeat(MAX_LINE_LENGTH as usize)
+        );
+        assert!(read_request(&mut line.as_bytes()).is_err());
+        let headers = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "A: b\r\n".repeat(MAX_HEADERS + 1)
+        );
+        assert!(read_request(&mut headers.as_bytes()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_serve_mirror() -> Result<()> {
+        let dir = TestDir::new("serve")?;
+        fs::create_dir_all(dir.join("eg"))?;
+        fs::write(dir.join("eg").join("tar.md"), "# tar")?;
+        fs::write(dir.join("secret"), "secret")?;
+        let server = MirrorServer::bind(dir.join("eg"), "127.0.0.1:0")?;
+        let url = format!("http://{}", server.local_addr()?);
+        thread::spawn(move || server.run());
+
+        let cache = PageCache::new(dir.join("cache"), Duration::ZERO);
+        let provider = eg::provider(Some(&url)).cache(Some(cache.clone()));
+        assert_eq!("# tar", provider.fetch("tar")?.content);
+        // Revalidated with the ETag.
+        assert_eq!("# tar", provider.fetch("tar")?.content);
+        assert!(cache
+            .get("eg", &provider.get_url("tar"))
+            .and_then(|entry| entry.etag)
+            .is_some());
+        assert!(provider.fetch("ls").is_err());
+        assert!(provider.fetch("../secret").is_err());
+        Ok(())
+    }
+}
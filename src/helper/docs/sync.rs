@@ -0,0 +1,350 @@
+use crate::config::Config;
+use crate::error::{Error, Result};
+use crate::helper::args::get_timeout;
+use crate::helper::args::{CHECK_EMOTICON, FAIL_EMOTICON, FOUND_EMOTICON};
+use crate::helper::docs::cheat_sh::CheatSh;
+use crate::helper::docs::http::build_agent;
+use crate::helper::docs::HelpProvider;
+use colored::*;
+use std::fs;
+use std::io::{Cursor, Read, Seek, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use zip::ZipArchive;
+
+/// Archive of the `cheatsheets` repository.
+pub const CHEATSHEETS_ARCHIVE_URL: &str =
+    "https://github.com/cheat/cheatsheets/archive/refs/heads/master.zip";
+
+/// Archive of the `eg` repository.
+pub const EG_ARCHIVE_URL: &str = "https://github.com/srsudar/eg/archive/refs/heads/master.zip";
+
+/// Archive of the `tldr` repository.
+pub const TLDR_ARCHIVE_URL: &str = "https://github.com/tldr-pages/tldr/archive/refs/heads/main.zip";
+
+/// Maximum size of the downloaded archives.
+const MAX_ARCHIVE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Maximum time for downloading an archive.
+const ARCHIVE_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Providers that can be synchronized.
+pub const SYNC_PROVIDERS: &[&str] = &["cheatsheets", "eg", "tldr", "cheat.sh"];
+
+/// Returns the directory of the local mirror (e.g. `<data_dir>/halp/mirror`).
+pub fn get_mirror_dir(config: &Config) -> Result<PathBuf> {
+    match config.mirror_dir {
+        Some(ref dir) => Ok(dir.to_path_buf()),
+        None => Ok(Config::get_data_dir()?.join("mirror")),
+    }
+}
+
+/// Returns the mirror directory of the given provider if it exists.
+pub fn get_provider_mirror(config: &Config, provider: &str) -> Option<PathBuf> {
+    get_mirror_dir(config)
+        .ok()
+        .map(|dir| dir.join(provider))
+        .filter(|dir| dir.is_dir())
+}
+
+/// Downloads the file at the given URL.
+///
+/// Connecting and waiting for the response are limited by the configured timeout, while the
+/// whole download is limited by [`ARCHIVE_DOWNLOAD_TIMEOUT`].
+fn download(url: &str, config: &Config) -> Result<Vec<u8>> {
+    let timeout = get_timeout(config);
+    let handle_error = |e: ureq::Error| match e {
+        ureq::Error::Timeout(_) => Error::TimeoutError(timeout),
+        e => Error::from(Box::new(e)),
+    };
+    build_agent(None, timeout)
+        .get(url)
+        .config()
+        .timeout_global(Some(ARCHIVE_DOWNLOAD_TIMEOUT))
+        .build()
+        .call()
+        .map_err(handle_error)?
+        .body_mut()
+        .with_config()
+        .limit(MAX_ARCHIVE_SIZE)
+        .read_to_vec()
+        .map_err(handle_error)
+}
+
+/// Opens the downloaded zip archive.
+pub fn open_archive(archive: &[u8]) -> Result<ZipArchive<Cursor<&[u8]>>> {
+    ZipArchive::new(Cursor::new(archive))
+        .map_err(|e| Error::ProviderError(format!("Invalid archive: {e}")))
+}
+
+/// Extracts the files from the zip archive into the given directory.
+///
+/// The top-level directory of the archive (e.g. `eg-master/`) and the given prefix are stripped
+/// from the paths and only the files that start with the prefix are extracted.
+///
+/// Returns the number of extracted files.
+pub fn extract_archive<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    prefix: &str,
+    dir: &Path,
+) -> Result<usize> {
+    let mut count = 0;
+    for i in 0..zip.len() {
+        let mut file = zip
+            .by_index(i)
+            .map_err(|e| Error::ProviderError(format!("Invalid archive: {e}")))?;
+        let Some(path) = file.enclosed_name() else {
+            continue;
+        };
+        let Some(path) = path
+            .strip_prefix(
+                path.components()
+                    .next()
+                    .map(|c| c.as_os_str())
+                    .unwrap_or_default(),
+            )
+            .ok()
+            .and_then(|path| path.strip_prefix(prefix).ok())
+            .filter(|path| !path.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+        else {
+            continue;
+        };
+        if file.is_dir() {
+            continue;
+        }
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        fs::write(path, contents)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Replaces the mirror directory of the provider with the files written by `populate`.
+///
+/// The files are written to a temporary directory first so that the existing mirror is kept on failure.
+fn replace_mirror<F: FnOnce(&Path) -> Result<usize>>(dir: &Path, populate: F) -> Result<usize> {
+    let mut tmp_dir = dir.as_os_str().to_owned();
+    tmp_dir.push(".tmp");
+    let tmp_dir = PathBuf::from(tmp_dir);
+    if tmp_dir.exists() {
+        fs::remove_dir_all(&tmp_dir)?;
+    }
+    fs::create_dir_all(&tmp_dir)?;
+    let count = match populate(&tmp_dir) {
+        Ok(count) => count,
+        Err(e) => {
+            fs::remove_dir_all(&tmp_dir)?;
+            return Err(e);
+        }
+    };
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    fs::rename(tmp_dir, dir)?;
+    Ok(count)
+}
+
+/// Synchronizes the local mirror of the given provider.
+///
+/// Returns the number of synchronized pages.
+pub fn sync_provider(provider: &str, mirror_dir: &Path, config: &Config) -> Result<usize> {
+    let dir = mirror_dir.join(provider);
+    match provider {
+        "cheatsheets" => replace_mirror(&dir, |dir| {
+            let archive = download(CHEATSHEETS_ARCHIVE_URL, config)?;
+            extract_archive(&mut open_archive(&archive)?, "", dir)
+        }),
+        "eg" => replace_mirror(&dir, |dir| {
+            let archive = download(EG_ARCHIVE_URL, config)?;
+            extract_archive(&mut open_archive(&archive)?, "eg/examples", dir)
+        }),
+        "tldr" => replace_mirror(&dir, |dir| {
+            let archive = download(TLDR_ARCHIVE_URL, config)?;
+            let mut zip = open_archive(&archive)?;
+            let mut count = 0;
+            for language in list_archive_dirs(&zip)
+                .iter()
+                .filter(|name| name.starts_with("pages"))
+            {
+                count += extract_archive(&mut zip, language, &dir.join(language))?;
+            }
+            Ok(count)
+        }),
+        "cheat.sh" => {
+            // The pages are fetched with the configured options (see `CheatSh`).
+            let cheat_sh = CheatSh::from_config(config);
+            replace_mirror(&dir, |dir| {
+                for topic in &config.cheat_sh_topics {
+                    if Path::new(topic)
+                        .components()
+                        .any(|c| !matches!(c, Component::Normal(_)))
+                    {
+                        return Err(Error::ProviderError(format!("Invalid topic: {topic}")));
+                    }
+                    let path = dir.join(topic);
+                    if let Some(parent) = path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(path, cheat_sh.fetch(topic)?.content)?;
+                }
+                Ok(config.cheat_sh_topics.len())
+            })
+        }
+        _ => Err(Error::ProviderError(format!(
+            "Cannot sync {provider}, available providers: {}",
+            SYNC_PROVIDERS.join(", ")
+        ))),
+    }
+}
+
+/// Returns the names of the directories in the top-level directory of the archive.
+fn list_archive_dirs<R: Read + Seek>(zip: &ZipArchive<R>) -> Vec<String> {
+    let mut dirs = zip
+        .file_names()
+        .filter_map(|name| {
+            name.split('/')
+                .nth(1)
+                .filter(|_| name.matches('/').count() > 1)
+        })
+        .map(String::from)
+        .collect::<Vec<_>>();
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// Downloads the pages of the given providers (or all providers) into the local mirror.
+///
+/// All providers are synchronized even if some of them fail, and an error that lists the failed
+/// ones is returned afterwards.
+pub fn sync_mirror<Output: Write>(
+    providers: &[String],
+    config: &Config,
+    output: &mut Output,
+) -> Result<()> {
+    let mirror_dir = get_mirror_dir(config)?;
+    let providers = if providers.is_empty() {
+        SYNC_PROVIDERS.iter().map(|v| v.to_string()).collect()
+    } else {
+        providers.to_vec()
+    };
+    let mut failed = Vec::new();
+    for provider in providers {
+        if provider == "cheat.sh" && config.cheat_sh_topics.is_empty() {
+            writeln!(
+                output,
+                "{}      {} no topics are configured for {} (see `cheat_sh_topics`)",
+                FAIL_EMOTICON.magenta(),
+                "skip".yellow().bold(),
+                provider.white().italic()
+            )?;
+            continue;
+        }
+        writeln!(
+            output,
+            "{}  {} '{}'",
+            CHECK_EMOTICON.magenta(),
+            "syncing".green().bold(),
+            provider.white().italic()
+        )?;
+        match sync_provider(&provider, &mirror_dir, config) {
+            Ok(count) => writeln!(
+                output,
+                "{} {} {} pages into {}",
+                FOUND_EMOTICON.magenta(),
+                "synced".cyan().bold(),
+                count,
+                format!("{:?}", mirror_dir.join(&provider)).white().italic()
+            )?,
+            Err(e) => {
+                writeln!(
+                    output,
+                    "{}      {} {}",
+                    FAIL_EMOTICON.magenta(),
+                    "fail".red().bold(),
+                    e.to_string().white().italic()
+                )?;
+                failed.push(provider);
+            }
+        }
+    }
+    if !failed.is_empty() {
+        return Err(Error::ProviderError(format!(
+            "Failed to sync {}",
+            failed.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+    use std::net::TcpListener;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    #[test]
+    fn test_extract_archive() -> Result<()> {
+        let mut archive = Vec::new();
+        {
+            let mut zip = ZipWriter::new(Cursor::new(&mut archive));
+            for (name, contents) in [
+                ("eg-master/README.md", "readme"),
+                ("eg-master/eg/examples/tar.md", "# tar"),
+                ("eg-master/eg/examples/git/commit.md", "# git commit"),
+            ] {
+                zip.start_file(name, SimpleFileOptions::default())
+                    .map_err(|e| Error::ProviderError(e.to_string()))?;
+                zip.write_all(contents.as_bytes())?;
+            }
+            zip.add_directory("eg-master/pages.de/", SimpleFileOptions::default())
+                .map_err(|e| Error::ProviderError(e.to_string()))?;
+            zip.finish()
+                .map_err(|e| Error::ProviderError(e.to_string()))?;
+        }
+        let dir = TestDir::new("sync")?;
+        let mut zip = open_archive(&archive)?;
+        assert_eq!(2, extract_archive(&mut zip, "eg/examples", &dir)?);
+        assert_eq!("# tar", fs::read_to_string(dir.join("tar.md"))?);
+        assert_eq!(
+            "# git commit",
+            fs::read_to_string(dir.join("git").join("commit.md"))?
+        );
+        assert!(!dir.join("README.md").exists());
+        assert_eq!(
+            vec![String::from("eg"), String::from("pages.de")],
+            list_archive_dirs(&zip)
+        );
+        let config = Config {
+            mirror_dir: Some(dir.join("mirror")),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        assert!(sync_mirror(&[String::from("unknown")], &config, &mut output).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_timeout() -> Result<()> {
+        // The connections are accepted by the kernel but never replied to.
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let config = Config {
+            timeout: Some(1),
+            ..Default::default()
+        };
+        let result = download(
+            &format!("http://{}/archive.zip", listener.local_addr()?),
+            &config,
+        );
+        assert!(matches!(result, Err(Error::TimeoutError(1))));
+        Ok(())
+    }
+}
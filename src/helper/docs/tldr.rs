@@ -0,0 +1,270 @@
+use crate::config::Config;
+use crate::error::{Error, ErrorKind, Result};
+use crate::helper::args::get_timeout;
+use crate::helper::docs::http::{percent_encode, HttpProvider};
+use crate::helper::docs::{ContentFormat, HelpPage, HelpProvider};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+/// The default tldr pages provider URL.
+pub const DEFAULT_TLDR_PROVIDER: &str = "https://raw.githubusercontent.com/tldr-pages/tldr/main";
+
+/// Platform directory that contains the pages for all platforms.
+const COMMON_PLATFORM: &str = "common";
+
+/// The `tldr` pages provider.
+///
+/// Pages are looked up in the platform directory first and then in the common directory.
+/// If a language is set, the translated pages are preferred over the English ones.
+#[derive(Debug)]
+pub struct Tldr {
+    /// Provider for fetching the remote pages.
+    pub http: HttpProvider,
+    /// Platform directory (e.g. `linux`, `osx`).
+    pub platform: Option<String>,
+    /// Language code (e.g. `de`, `pt_BR`).
+    pub language: Option<String>,
+    /// Path of the local tldr archive (zip file or directory).
+    pub archive: Option<PathBuf>,
+}
+
+impl Tldr {
+    /// Creates a new provider with the given URL (defaults to [`DEFAULT_TLDR_PROVIDER`]).
+    pub fn new(url: Option<&str>) -> Self {
+        Self {
+            http: HttpProvider::new("tldr", url.unwrap_or(DEFAULT_TLDR_PROVIDER))
+                .format(ContentFormat::Markdown)
+                .raw_query(),
+            platform: None,
+            language: None,
+            archive: None,
+        }
+    }
+
+    /// Creates a new provider from the configuration.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            http: Self::new(config.tldr_url.as_deref())
+                .http
+                .timeout(get_timeout(config)),
+            platform: config.tldr_platform.clone(),
+            language: config.tldr_language.clone(),
+            archive: config.tldr_archive.clone(),
+        }
+    }
+
+    /// Returns the platform directory of the current operating system.
+    pub fn get_default_platform() -> &'static str {
+        if cfg!(target_os = "android") {
+            "android"
+        } else if cfg!(target_os = "linux") {
+            "linux"
+        } else if cfg!(target_os = "macos") {
+            "osx"
+        } else if cfg!(target_os = "windows") {
+            "windows"
+        } else if cfg!(target_os = "freebsd") {
+            "freebsd"
+        } else if cfg!(target_os = "openbsd") {
+            "openbsd"
+        } else if cfg!(target_os = "netbsd") {
+            "netbsd"
+        } else {
+            COMMON_PLATFORM
+        }
+    }
+
+    /// Returns the candidate paths of the page in the order of preference.
+    ///
+    /// e.g. `pages.de/linux/tar.md`, `pages.de/common/tar.md`, `pages/linux/tar.md`, ...
+    pub fn get_page_paths(&self, cmd: &str) -> Vec<String> {
+        let platform = self
+            .platform
+            .as_deref()
+            .unwrap_or_else(|| Self::get_default_platform());
+        let mut platforms = vec![platform];
+        if platform != COMMON_PLATFORM {
+            platforms.push(COMMON_PLATFORM);
+        }
+        let mut page_dirs = Vec::new();
+        if let Some(language) = self.language.as_ref().filter(|l| !l.starts_with("en")) {
+            page_dirs.push(format!("pages.{language}"));
+        }
+        page_dirs.push(String::from("pages"));
+        page_dirs
+            .iter()
+            .flat_map(|dir| {
+                platforms
+                    .iter()
+                    .map(move |platform| format!("{dir}/{platform}/{cmd}.md"))
+            })
+            .collect()
+    }
+
+    /// Reads the page from the local archive.
+    ///
+    /// Returns `None` if the page is not found in the archive.
+    fn read_archive(&self, archive: &Path, cmd: &str) -> Result<Option<String>> {
+        let paths = self.get_page_paths(cmd);
+        if archive.is_dir() {
+            for path in paths {
+                let path = archive.join(path);
+                if path.is_file() {
+                    return Ok(Some(fs::read_to_string(path)?));
+                }
+            }
+            return Ok(None);
+        }
+        let mut zip = ZipArchive::new(File::open(archive)?)
+            .map_err(|e| Error::ProviderError(format!("Invalid tldr archive: {e}")))?;
+        for path in paths {
+            // Pages might be nested in a directory (e.g. `tldr-main/pages/...`).
+            let name = zip
+                .file_names()
+                .find(|name| *name == path || name.ends_with(&format!("/{path}")))
+                .map(String::from);
+            if let Some(name) = name {
+                let mut page = String::new();
+                zip.by_name(&name)
+                    .map_err(|e| Error::ProviderError(format!("Invalid tldr archive: {e}")))?
+                    .read_to_string(&mut page)?;
+                return Ok(Some(page));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl HelpProvider for Tldr {
+    fn name(&self) -> &str {
+        "tldr"
+    }
+
+    fn fetch(&self, cmd: &str) -> Result<HelpPage> {
+        if let Some(ref archive) = self.archive {
+            if let Some(content) = self.read_archive(archive, cmd)? {
+                return Ok(HelpPage {
+                    provider: self.name().to_string(),
+                    content,
+                    format: ContentFormat::Markdown,
+                    ..Default::default()
+                });
+            }
+        }
+        // Every path is tried even if the provider is unreachable, since the cache might have a
+        // stale copy of a later one (e.g. `pages/common/tar.md`).
+        let mut not_found = None;
+        let mut error = None;
+        for path in self.get_page_paths(&percent_encode(cmd, b"")) {
+            match self.http.fetch(&path) {
+                Ok(page) => return Ok(page),
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    not_found.get_or_insert(e);
+                }
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        Err(error
+            .or(not_found)
+            .unwrap_or_else(|| Error::NotFoundError(String::from("Unknown topic."))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helper::docs::cache::{now, CacheEntry, PageCache};
+    use crate::helper::test_dir::TestDir;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+    use std::time::Duration;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    #[test]
+    fn test_page_paths() {
+        let tldr = Tldr {
+            platform: Some(String::from("osx")),
+            language: Some(String::from("de")),
+            ..Tldr::new(None)
+        };
+        assert_eq!(
+            vec![
+                "pages.de/osx/tar.md",
+                "pages.de/common/tar.md",
+                "pages/osx/tar.md",
+                "pages/common/tar.md",
+            ],
+            tldr.get_page_paths("tar")
+        );
+    }
+
+    #[test]
+    fn test_read_archive() -> Result<()> {
+        let dir = TestDir::new("tldr")?;
+        fs::create_dir_all(dir.join("pages").join("common"))?;
+        fs::write(dir.join("pages").join("common").join("tar.md"), "# tar")?;
+        let tldr = Tldr {
+            platform: Some(String::from("linux")),
+            archive: Some(dir.to_path_buf()),
+            ..Tldr::new(None)
+        };
+        assert_eq!("# tar", tldr.fetch("tar")?.content);
+
+        let zip_path = dir.join("tldr.zip");
+        let mut zip = ZipWriter::new(File::create(&zip_path)?);
+        zip.start_file("tldr-main/pages/linux/tar.md", SimpleFileOptions::default())
+            .map_err(|e| Error::ProviderError(e.to_string()))?;
+        zip.write_all(b"# tar (linux)")?;
+        zip.finish()
+            .map_err(|e| Error::ProviderError(e.to_string()))?;
+        let tldr = Tldr {
+            archive: Some(zip_path),
+            ..tldr
+        };
+        assert_eq!("# tar (linux)", tldr.fetch("tar")?.content);
+        assert_eq!(None, tldr.read_archive(&dir.join("tldr.zip"), "ls")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_offline() -> Result<()> {
+        let dir = TestDir::new("tldr-offline")?;
+        let cache = PageCache::new(dir.to_path_buf(), Duration::from_secs(60));
+        cache.set(
+            "tldr",
+            "http://127.0.0.1:1/pages/common/tar.md",
+            &CacheEntry {
+                content: String::from("# tar"),
+                format: ContentFormat::Markdown,
+                etag: None,
+                last_modified: None,
+                fetched_at: now().as_secs() - 3600,
+            },
+        )?;
+        // Nothing listens on this port so the requests fail.
+        let mut tldr = Tldr {
+            platform: Some(String::from("linux")),
+            ..Tldr::new(Some("http://127.0.0.1:1"))
+        };
+        tldr.http = tldr.http.cache(Some(cache));
+        let page = tldr.fetch("tar")?;
+        assert_eq!("# tar", page.content);
+        assert!(page.stale_age.is_some());
+        assert_eq!(
+            Some(ErrorKind::Offline),
+            tldr.fetch("ls").err().map(|e| e.kind())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fetch_tldr_page() -> Result<()> {
+        let output = Tldr::new(None).fetch("tar")?.content;
+        assert!(output.contains("> Archiving utility."));
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+
+/// Returns the names of the executables found in `PATH`.
+pub fn find_executables() -> Vec<String> {
+    let mut executables = BTreeSet::new();
+    let Some(paths) = env::var_os("PATH") else {
+        return Vec::new();
+    };
+    for dir in env::split_paths(&paths) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_file() && is_executable(&metadata) {
+                if let Some(name) = entry.file_name().to_str() {
+                    executables.insert(name.to_string());
+                }
+            }
+        }
+    }
+    executables.into_iter().collect()
+}
+
+/// Returns `true` if the file is executable.
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+/// Returns `true` if the file is executable.
+#[cfg(not(unix))]
+fn is_executable(_: &fs::Metadata) -> bool {
+    true
+}
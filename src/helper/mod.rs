@@ -6,3 +6,10 @@ pub mod docs;
 
 /// Command executor for TTY.
 pub mod tty;
+
+/// Executables in `PATH`.
+pub mod executables;
+
+/// Temporary directories for the tests.
+#[cfg(test)]
+pub mod test_dir;
@@ -0,0 +1,51 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counter for making the names of the directories unique within the process.
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Temporary directory for the tests.
+///
+/// The directory is removed when it is dropped, so it is cleaned up even if the test fails.
+#[derive(Debug)]
+pub struct TestDir(PathBuf);
+
+impl TestDir {
+    /// Creates an empty directory with the given name (e.g. `halp-cache-<pid>-<n>`).
+    pub fn new(name: &str) -> io::Result<Self> {
+        let path = env::temp_dir().join(format!(
+            "halp-{name}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        if path.exists() {
+            fs::remove_dir_all(&path)?;
+        }
+        fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+}
+
+impl Deref for TestDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TestDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
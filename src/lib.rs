@@ -17,11 +17,28 @@ pub mod config;
 use crate::cli::CliArgs;
 use crate::error::Result;
 use crate::helper::args::FAIL_EMOTICON;
-use cli::CliCommands;
+use cli::{CliCommands, IndexCommand, NoteCommand, PluginCommand};
 use colored::*;
 use config::Config;
+use helper::args::completions::generate_completions;
+use helper::args::explain::explain_command_line;
+use helper::args::export::export_spec;
 use helper::args::get_args_help;
-use helper::docs::get_docs_help;
+use helper::args::grep::grep_help;
+use helper::args::index::{build_index, search_index};
+use helper::args::mangen::generate_man_page;
+use helper::docs::cheat::list_cheat_sheets;
+use helper::docs::cheat_sh::{list_query, search_query, CHEAT_SH_PROVIDER};
+use helper::docs::info::navigate_info;
+use helper::docs::man::apropos;
+use helper::docs::notes::{edit_note, export_notes, show_note};
+use helper::docs::plugin::{list_plugins, search_plugins};
+use helper::docs::serve::serve_mirror;
+use helper::docs::sync::{get_mirror_dir, sync_mirror};
+use helper::docs::{
+    get_docs_all, get_docs_examples, get_docs_first, get_docs_help, is_interactive, print_docs,
+};
+use helper::executables::find_executables;
 use std::io::Write;
 
 /// Runs `halp`.
@@ -46,9 +63,117 @@ pub fn run<Output: Write>(cli_args: CliArgs, output: &mut Output) -> Result<()>
     };
     cli_args.update_config(&mut config);
     if let Some(ref cmd) = cli_args.cmd {
-        get_args_help(cmd, &config, cli_args.verbose, output)?;
-    } else if let Some(CliCommands::Plz { ref cmd, .. }) = cli_args.subcommand {
-        get_docs_help(cmd, &config, output)?;
+        if let Some(ref pattern) = cli_args.grep {
+            grep_help(
+                cmd,
+                pattern,
+                cli_args.fuzzy,
+                cli_args.recursive,
+                &config,
+                output,
+            )?;
+        } else {
+            get_args_help(cmd, &config, cli_args.verbose, output)?;
+        }
+        if cli_args.note {
+            show_note(cmd, &config, output)?;
+        }
+    } else if let Some(ref subcommand) = cli_args.subcommand {
+        match subcommand {
+            CliCommands::Plz {
+                cmd,
+                examples,
+                all,
+                first,
+                search,
+                list,
+                provider,
+                ..
+            } => {
+                let cheat_sh = [String::from(CHEAT_SH_PROVIDER)];
+                if let Some(keyword) = search {
+                    get_docs_first(&search_query(cmd, keyword), &cheat_sh, &config, output)?
+                } else if *list {
+                    get_docs_first(&list_query(cmd), &cheat_sh, &config, output)?
+                } else if *first {
+                    get_docs_first(cmd, provider, &config, output)?
+                } else if *all {
+                    get_docs_all(cmd, &config, output)?
+                } else if *examples {
+                    get_docs_examples(cmd, &config, output)?
+                } else if !provider.is_empty() || !is_interactive() {
+                    print_docs(cmd, provider, &config, output)?
+                } else {
+                    get_docs_help(cmd, &config, output)?
+                }
+            }
+            CliCommands::Explain { cmdline } => explain_command_line(cmdline, &config, output)?,
+            CliCommands::Index { file, command } => match command {
+                IndexCommand::Build {
+                    cmds,
+                    all,
+                    recursive,
+                } => {
+                    let cmds = if *all {
+                        find_executables()
+                    } else {
+                        cmds.to_vec()
+                    };
+                    build_index(&cmds, *recursive, file.clone(), &config, output)?
+                }
+                IndexCommand::Search { query, limit } => {
+                    search_index(query, *limit, file.clone(), output)?
+                }
+            },
+            CliCommands::Completions {
+                cmd,
+                shell,
+                recursive,
+            } => generate_completions(cmd, *shell, *recursive, &config, output)?,
+            CliCommands::Mangen {
+                cmd,
+                section,
+                output: out_file,
+                install,
+                recursive,
+            } => generate_man_page(
+                cmd,
+                section,
+                *recursive,
+                out_file.clone(),
+                *install,
+                &config,
+                output,
+            )?,
+            CliCommands::Export {
+                cmd,
+                format,
+                output: out_file,
+                recursive,
+            } => export_spec(cmd, *format, *recursive, out_file.clone(), &config, output)?,
+            CliCommands::Sync { providers } => sync_mirror(providers, &config, output)?,
+            CliCommands::Info { query } => navigate_info(query, &config, output)?,
+            CliCommands::Apropos { keyword } => apropos(keyword, output)?,
+            CliCommands::Sheets { tag } => {
+                list_cheat_sheets(&config.cheatpaths, tag.as_deref(), output)?
+            }
+            CliCommands::Serve { mirror, address } => {
+                let dir = match mirror {
+                    Some(dir) => dir.to_path_buf(),
+                    None => get_mirror_dir(&config)?,
+                };
+                serve_mirror(dir, address, output)?
+            }
+            CliCommands::Note { cmd, command } => match command {
+                Some(NoteCommand::Export { dir }) => export_notes(dir, &config, output)?,
+                // The command is required by the parser unless a subcommand is given.
+                None => edit_note(cmd.as_deref().unwrap_or_default(), &config, output)?,
+            },
+            CliCommands::Plugins { command } => match command {
+                Some(PluginCommand::Search { query }) => search_plugins(query, &config, output)?,
+                Some(PluginCommand::List) | None => list_plugins(&config, output)?,
+            },
+        }
     }
     Ok(())
 }